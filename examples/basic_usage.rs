@@ -1,4 +1,4 @@
-use rustboxd::{User, Movie, Search, Films, List};
+use rustboxd::{User, Movie, Search};
 use std::error::Error;
 
 #[tokio::main]
@@ -28,10 +28,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
             if let Some(year) = movie.year {
                 println!("Year: {}", year);
             }
-            if let Some(rating) = movie.rating {
+            if let Some(rating) = movie.average_rating {
                 println!("Rating: {}/5", rating);
             }
-            println!("Genres: {:?}", movie.genres);
+            let genres: Vec<_> = movie.genres.iter().map(|genre| genre.name.as_str()).collect();
+            println!("Genres: {}", genres.join(", "));
         }
         Err(e) => println!("Error fetching movie: {}", e),
     }
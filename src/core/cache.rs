@@ -0,0 +1,293 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use crate::core::{Result, cassette::fnv1a, transport::{HttpRequest, HttpResponse}};
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+
+/// On-disk cache of page bodies for a [`Client`](crate::Client).
+///
+/// Every successful GET is stored under `dir`, keyed by URL. An entry is
+/// served without a request while it is younger than the TTL of its route;
+/// after that it is revalidated with `If-None-Match` / `If-Modified-Since`
+/// when the server sent an `ETag` or `Last-Modified` header.
+///
+/// Routes are matched by a substring of the URL path. By default film pages
+/// themselves are kept for a week, diaries for an hour, and a film's live
+/// stats, members and reviews for ten minutes; everything else for a day.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use rustboxd::{Client, core::HttpCache};
+///
+/// # fn main() -> rustboxd::Result<()> {
+/// let client = Client::builder()
+///     .cache(HttpCache::new(".rustboxd-cache")
+///         .route_ttl("/watchlist/", Duration::from_secs(600))
+///         .max_size(200 * 1024 * 1024))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+    default_ttl: Duration,
+    /// `(path pattern, ttl)`, first match wins
+    routes: Vec<(Route, Duration)>,
+    max_size: Option<u64>,
+    max_entry_size: Option<u64>,
+}
+
+/// Which URL paths a TTL applies to
+#[derive(Debug, Clone)]
+enum Route {
+    /// Added with [`HttpCache::route_ttl`]
+    Contains(String),
+    /// Built-in routes, which need anchoring to tell a film page from its sub-pages
+    Matches(Regex),
+}
+
+impl Route {
+    fn built_in(pattern: &str) -> Self {
+        Route::Matches(Regex::new(pattern).unwrap())
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        match self {
+            Route::Contains(pattern) => path.contains(pattern.as_str()),
+            Route::Matches(regex) => regex.is_match(path),
+        }
+    }
+}
+
+/// A cached response body and the validators needed to revalidate it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub url: String,
+    /// Seconds since the Unix epoch when the entry was stored or last revalidated
+    pub stored_at: u64,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+impl HttpCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            default_ttl: Duration::from_secs(24 * HOUR),
+            routes: vec![
+                (Route::built_in(r"^/csi/film/"), Duration::from_secs(10 * MINUTE)),
+                (
+                    Route::built_in(r"^/film/[^/]+/(members|likes|ratings|reviewers|watchlist|fans|reviews)/"),
+                    Duration::from_secs(10 * MINUTE),
+                ),
+                (Route::built_in(r"/diary/"), Duration::from_secs(HOUR)),
+                (Route::built_in(r"^/film/[^/]+/$"), Duration::from_secs(7 * 24 * HOUR)),
+            ],
+            max_size: None,
+            max_entry_size: None,
+        }
+    }
+
+    /// TTL for URLs that match no route
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    /// TTL for URLs whose path contains `pattern`.
+    ///
+    /// Routes added later take precedence over earlier ones and over the
+    /// built-in routes.
+    pub fn route_ttl(mut self, pattern: &str, ttl: Duration) -> Self {
+        self.routes.insert(0, (Route::Contains(pattern.to_string()), ttl));
+        self
+    }
+
+    /// Evict the least recently stored entries once the cache exceeds this many bytes
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Never store bodies larger than this many bytes
+    pub fn max_entry_size(mut self, bytes: u64) -> Self {
+        self.max_entry_size = Some(bytes);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// How long a response for `url` stays fresh
+    pub fn ttl_for(&self, url: &str) -> Duration {
+        let path = url::Url::parse(url)
+            .map(|u| u.path().to_string())
+            .unwrap_or_else(|_| url.to_string());
+
+        self.routes
+            .iter()
+            .find(|(route, _)| route.matches(&path))
+            .map(|(_, ttl)| *ttl)
+            .unwrap_or(self.default_ttl)
+    }
+
+    /// Path of the entry for `url` inside the cache directory
+    pub fn entry_path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(url.as_bytes())))
+    }
+
+    /// Cached entry for `url`, fresh or not. Unreadable entries count as misses.
+    pub async fn get(&self, url: &str) -> Option<CacheEntry> {
+        let contents = tokio::fs::read(self.entry_path(url)).await.ok()?;
+        serde_json::from_slice::<CacheEntry>(&contents)
+            .ok()
+            .filter(|entry| entry.url == url)
+    }
+
+    /// Whether `entry` can be served without contacting the server
+    pub fn is_fresh(&self, entry: &CacheEntry) -> bool {
+        now().saturating_sub(entry.stored_at) < self.ttl_for(&entry.url).as_secs()
+    }
+
+    /// Add conditional headers for revalidating `entry` to `request`
+    pub fn revalidate(&self, request: HttpRequest, entry: &CacheEntry) -> HttpRequest {
+        let request = match &entry.etag {
+            Some(etag) => request.header("If-None-Match", etag),
+            None => request,
+        };
+        match &entry.last_modified {
+            Some(last_modified) => request.header("If-Modified-Since", last_modified),
+            None => request,
+        }
+    }
+
+    /// Store a `200 OK` response for `url`
+    pub async fn store(&self, url: &str, response: &HttpResponse) -> Result<()> {
+        if self.max_entry_size.is_some_and(|max| response.body.len() as u64 > max) {
+            return Ok(());
+        }
+
+        self.write(&CacheEntry {
+            url: url.to_string(),
+            stored_at: now(),
+            etag: response.header("etag").map(str::to_string),
+            last_modified: response.header("last-modified").map(str::to_string),
+            body: response.body.clone(),
+        }).await
+    }
+
+    /// Mark `entry` as fresh again after the server answered `304 Not Modified`
+    pub async fn refresh(&self, mut entry: CacheEntry) -> Result<CacheEntry> {
+        entry.stored_at = now();
+        self.write(&entry).await?;
+        Ok(entry)
+    }
+
+    async fn write(&self, entry: &CacheEntry) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        // Write to a temporary file first so concurrent readers never see a partial entry
+        let path = self.entry_path(&entry.url);
+        let temp = path.with_extension(format!("{}.tmp", TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+        tokio::fs::write(&temp, serde_json::to_vec(entry)?).await?;
+        tokio::fs::rename(&temp, &path).await?;
+
+        if let Some(max_size) = self.max_size {
+            self.evict(max_size).await?;
+        }
+        Ok(())
+    }
+
+    /// Remove the oldest entries until the cache fits in `max_size` bytes.
+    ///
+    /// Other clients sharing the directory may be evicting at the same time,
+    /// so entries that disappear while we look at them are skipped.
+    async fn evict(&self, max_size: u64) -> Result<()> {
+        let mut entries = Vec::new();
+        let mut dir = tokio::fs::read_dir(&self.dir).await?;
+        while let Some(file) = dir.next_entry().await? {
+            if file.path().extension().is_some_and(|ext| ext == "json") {
+                let Ok(metadata) = file.metadata().await else {
+                    continue;
+                };
+                entries.push((metadata.modified()?, metadata.len(), file.path()));
+            }
+        }
+
+        let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+        entries.sort_by_key(|(modified, _, _)| *modified);
+
+        for (_, len, path) in entries {
+            if total <= max_size {
+                break;
+            }
+            match tokio::fs::remove_file(&path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => total -= len,
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove every entry
+    pub async fn clear(&self) -> Result<()> {
+        match tokio::fs::remove_dir_all(&self.dir).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_ttls() {
+        let cache = HttpCache::new("cache").route_ttl("/watchlist/", Duration::from_secs(60));
+
+        assert_eq!(cache.ttl_for("https://letterboxd.com/film/the-matrix/"), Duration::from_secs(7 * 24 * HOUR));
+        assert_eq!(cache.ttl_for("https://letterboxd.com/testuser/films/diary/"), Duration::from_secs(HOUR));
+        assert_eq!(cache.ttl_for("https://letterboxd.com/testuser/watchlist/"), Duration::from_secs(60));
+        assert_eq!(cache.ttl_for("https://letterboxd.com/testuser/"), Duration::from_secs(24 * HOUR));
+        assert_eq!(cache.ttl_for("https://letterboxd.com/csi/film/the-matrix/stats/"), Duration::from_secs(10 * MINUTE));
+        assert_eq!(cache.ttl_for("https://letterboxd.com/film/the-matrix/members/"), Duration::from_secs(10 * MINUTE));
+        assert_eq!(cache.ttl_for("https://letterboxd.com/film/the-matrix/reviews/by/activity/"), Duration::from_secs(10 * MINUTE));
+        assert_eq!(cache.ttl_for("https://letterboxd.com/testuser/film/the-matrix/"), Duration::from_secs(24 * HOUR));
+    }
+
+    #[tokio::test]
+    async fn test_eviction_keeps_cache_under_max_size() {
+        let dir = std::env::temp_dir().join(format!("rustboxd-cache-evict-{}", std::process::id()));
+        let cache = HttpCache::new(&dir).max_size(1500);
+        let body = "x".repeat(600);
+
+        for i in 0..3 {
+            let url = format!("https://letterboxd.com/film/{}/", i);
+            cache.store(&url, &HttpResponse::ok(&url, &body)).await.unwrap();
+            // Give each entry a distinct modification time
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        assert!(cache.get("https://letterboxd.com/film/0/").await.is_none());
+        assert!(cache.get("https://letterboxd.com/film/2/").await.is_some());
+
+        cache.clear().await.unwrap();
+    }
+}
@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::{Error, Result, transport::{HttpRequest, HttpResponse, Method, Transport}};
+
+/// Record-and-replay mode for a [`Client`](crate::Client).
+///
+/// In `Record` mode every response (status, headers and body) is written to
+/// the directory as it is fetched, minus any cookies. In `Replay` mode responses are served from
+/// that directory only, and a URL without a recording is an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CassetteMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// One recorded exchange, stored as a JSON file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    /// URL as requested, which may differ from `response.url` after redirects
+    pub request_url: String,
+    pub response: HttpResponse,
+}
+
+/// Path of the recording for a `method` request to `url` inside `dir`.
+///
+/// File names start with a readable form of the URL and end with a hash of
+/// the full URL, so they are stable across runs and platforms. Requests other
+/// than GET are prefixed with their method so they never overwrite the GET
+/// recording of the same URL.
+pub fn recording_path(dir: &Path, method: Method, url: &str) -> PathBuf {
+    let readable: String = url
+        .split("://")
+        .last()
+        .unwrap_or(url)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .take(80)
+        .collect();
+    let readable = readable.trim_matches('_');
+
+    match method {
+        Method::Get => dir.join(format!("{}-{:016x}.json", readable, fnv1a(url.as_bytes()))),
+        Method::Post => dir.join(format!("post_{}-{:016x}.json", readable, fnv1a(format!("POST {}", url).as_bytes()))),
+    }
+}
+
+/// Headers that carry session tokens and must never reach a cassette
+const SECRET_HEADERS: [&str; 2] = ["set-cookie", "cookie"];
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Transport that forwards to another transport and records every response.
+///
+/// Cookie headers are stripped before writing, so recording a login does not
+/// put the session token in the cassette.
+#[derive(Debug)]
+pub struct RecordingTransport {
+    inner: Arc<dyn Transport>,
+    dir: PathBuf,
+}
+
+impl RecordingTransport {
+    pub fn new(inner: Arc<dyn Transport>, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            dir: dir.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for RecordingTransport {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let response = self.inner.execute(request.clone()).await?;
+
+        let mut recorded = response.clone();
+        recorded.headers.retain(|(name, _)| !SECRET_HEADERS.iter().any(|secret| name.eq_ignore_ascii_case(secret)));
+
+        let recording = Recording {
+            request_url: request.url.clone(),
+            response: recorded,
+        };
+        tokio::fs::create_dir_all(&self.dir).await?;
+        tokio::fs::write(
+            recording_path(&self.dir, request.method, &request.url),
+            serde_json::to_vec_pretty(&recording)?,
+        ).await?;
+
+        Ok(response)
+    }
+}
+
+/// Transport that serves recorded responses from disk and never hits the network
+#[derive(Debug)]
+pub struct ReplayTransport {
+    dir: PathBuf,
+}
+
+impl ReplayTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl Transport for ReplayTransport {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let path = recording_path(&self.dir, request.method, &request.url);
+
+        let contents = tokio::fs::read(&path).await.map_err(|_| Error::RecordingMissing {
+            url: request.url.clone(),
+            path: path.display().to_string(),
+        })?;
+        let recording: Recording = serde_json::from_slice(&contents)?;
+
+        Ok(recording.response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_path_is_stable_and_readable() {
+        let dir = Path::new("fixtures");
+        let path = recording_path(dir, Method::Get, "https://letterboxd.com/film/the-matrix/");
+
+        assert_eq!(path, recording_path(dir, Method::Get, "https://letterboxd.com/film/the-matrix/"));
+        assert_ne!(path, recording_path(dir, Method::Get, "https://letterboxd.com/film/the-matrix"));
+        assert_ne!(path, recording_path(dir, Method::Post, "https://letterboxd.com/film/the-matrix/"));
+
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("letterboxd_com_film_the-matrix-"));
+        assert!(name.ends_with(".json"));
+    }
+}
@@ -29,6 +29,18 @@ pub const DOMAIN: &str = "https://letterboxd.com";
 pub const SITE: &str = "https://letterboxd.com/";
 pub const SITE_SHORT: &str = "https://boxd.it/";
 
+// HTTP Constants
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64)";
+
+// Authentication Constants
+pub const SIGN_IN_PATH: &str = "sign-in/";
+pub const LOGIN_PATH: &str = "user/login.do";
+pub const LOGOUT_PATH: &str = "user/logout.do";
+/// Form field carrying the CSRF token on every authenticated POST
+pub const CSRF_FIELD: &str = "__csrf";
+pub const CSRF_COOKIE: &str = "com.xk72.webparts.csrf";
+pub const SESSION_COOKIE: &str = "letterboxd.user.CURRENT";
+
 pub const DOMAIN_MATCHES: [&str; 2] = ["letterboxd.com/", "boxd.it/"];
 
 // Movie-Related Constants
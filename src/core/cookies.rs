@@ -0,0 +1,130 @@
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+use std::sync::Arc;
+use cookie_store::CookieStore;
+use reqwest_cookie_store::CookieStoreMutex;
+use crate::core::{Error, Result};
+
+/// Cookie jar shared by a [`Client`](crate::Client) and all its clones.
+///
+/// Cookies set by responses are stored automatically. A jar can be saved to
+/// and loaded from a JSON file, so cookies survive between runs or can be
+/// seeded from a browser (for example a consent-wall cookie).
+///
+/// ```no_run
+/// use rustboxd::{Client, core::CookieJar};
+///
+/// # fn main() -> rustboxd::Result<()> {
+/// let jar = CookieJar::load("cookies.json")?;
+/// jar.add("cookie-consent=accepted; Domain=letterboxd.com; Path=/", "https://letterboxd.com/")?;
+///
+/// let client = Client::builder().cookie_jar(jar.clone()).build()?;
+/// // ... make requests ...
+/// jar.save("cookies.json")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    store: Arc<CookieStoreMutex>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a jar saved with [`CookieJar::save`], or an empty jar if the file does not exist
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let store = cookie_store::serde::json::load_all(BufReader::new(file))
+            .map_err(|e| Error::InvalidConfig(format!("Invalid cookie jar {}: {}", path.display(), e)))?;
+
+        Ok(Self {
+            store: Arc::new(CookieStoreMutex::new(store)),
+        })
+    }
+
+    /// Save every cookie, including session cookies, to `path` as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        let store = self.store.lock().unwrap();
+
+        cookie_store::serde::json::save_incl_expired_and_nonpersistent(&store, &mut writer)
+            .map_err(std::io::Error::other)?;
+        Ok(())
+    }
+
+    /// Add a cookie in `Set-Cookie` syntax as if it had been set by `url`
+    pub fn add(&self, cookie: &str, url: &str) -> Result<()> {
+        let url = url::Url::parse(url)?;
+        self.store
+            .lock()
+            .unwrap()
+            .parse(cookie, &url)
+            .map_err(|e| Error::InvalidConfig(format!("Invalid cookie {}: {}", cookie, e)))?;
+        Ok(())
+    }
+
+    /// Value of the unexpired cookie `name` that would be sent to `url`
+    pub fn get(&self, url: &str, name: &str) -> Option<String> {
+        let url = url::Url::parse(url).ok()?;
+        self.store
+            .lock()
+            .unwrap()
+            .get_request_values(&url)
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| value.to_string())
+    }
+
+    /// Remove every cookie
+    pub fn clear(&self) {
+        self.store.lock().unwrap().clear();
+    }
+
+    pub(crate) fn store(&self) -> Arc<CookieStoreMutex> {
+        self.store.clone()
+    }
+}
+
+impl From<CookieStore> for CookieJar {
+    fn from(store: CookieStore) -> Self {
+        Self {
+            store: Arc::new(CookieStoreMutex::new(store)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let path = std::env::temp_dir().join(format!("rustboxd-cookies-{}.json", std::process::id()));
+        let jar = CookieJar::new();
+        jar.add("letterboxd.user.CURRENT=abc123; Path=/", "https://letterboxd.com/").unwrap();
+        jar.add("com.xk72.webparts.csrf=token; Path=/", "https://letterboxd.com/").unwrap();
+
+        jar.save(&path).unwrap();
+        let loaded = CookieJar::load(&path).unwrap();
+
+        assert_eq!(loaded.get("https://letterboxd.com/film/alien/", "letterboxd.user.CURRENT").as_deref(), Some("abc123"));
+        assert_eq!(loaded.get("https://example.com/", "letterboxd.user.CURRENT"), None);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_missing_file_gives_empty_jar() {
+        let jar = CookieJar::load("/nonexistent/rustboxd-cookies.json").unwrap();
+
+        assert_eq!(jar.get("https://letterboxd.com/", "anything"), None);
+    }
+}
@@ -1,36 +1,190 @@
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
-    
+
     #[error("Failed to parse HTML")]
     HtmlParse,
-    
+
     #[error("Page load error for URL {url}: {message}")]
     PageLoad { url: String, message: String },
-    
+
+    #[error("Connection error for URL {url}: {message}")]
+    Connection { url: String, message: String },
+
+    #[error("Request timed out for URL {url}")]
+    Timeout { url: String },
+
+    #[error("Page not found: {url}")]
+    NotFound { url: String },
+
+    #[error("Rate limited by server for URL {url}")]
+    RateLimited { url: String, retry_after: Option<Duration> },
+
+    #[error("HTTP {status} for URL {url}")]
+    HttpStatus { status: u16, url: String, retry_after: Option<Duration> },
+
     #[error("Invalid response from server")]
     InvalidResponse,
-    
+
     #[error("Private route access denied")]
     PrivateRoute,
-    
+
     #[error("Invalid username: {0}")]
     InvalidUsername(String),
-    
+
+    #[error("User not found: {0}")]
+    UserNotFound(String),
+
     #[error("Movie not found: {0}")]
     MovieNotFound(String),
-    
-    #[error("Parsing error: {0}")]
-    Parse(String),
-    
+
+    #[error("Failed to parse {selector} on {url}: {context}")]
+    Parse { url: String, selector: String, context: String },
+
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
-    
+
     #[error("URL parsing error: {0}")]
     UrlParse(#[from] url::ParseError),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("No recorded response for {url} (expected {path})")]
+    RecordingMissing { url: String, path: String },
+
+    #[error("Login failed: {0}")]
+    LoginFailed(String),
+
+    #[error("Session expired and signing in again failed")]
+    SessionExpired,
+
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    #[error("Form rejected by the server: {0}")]
+    FormRejected(String),
+
+    #[error("Invalid client configuration: {0}")]
+    InvalidConfig(String),
+
+    #[error("Request to {url} failed after {attempts} attempts: {source}")]
+    RetriesExhausted { url: String, attempts: u32, source: Box<Error> },
+}
+
+impl Error {
+    /// The element `selector` on `url` was missing or malformed
+    pub(crate) fn parse(url: &str, selector: &str, context: impl Into<String>) -> Self {
+        Error::Parse {
+            url: url.to_string(),
+            selector: selector.to_string(),
+            context: context.into(),
+        }
+    }
+
+    /// Whether the failure is transient and the request may succeed if retried
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Connection { .. } | Error::Timeout { .. } | Error::RateLimited { .. } => true,
+            Error::HttpStatus { status, .. } => crate::core::retry::is_retryable_status(*status),
+            Error::Http(e) => e.is_timeout() || e.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// Whether the requested page, user or film does not exist
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Error::NotFound { .. } | Error::UserNotFound(_) | Error::MovieNotFound(_) => true,
+            Error::RetriesExhausted { source, .. } => source.is_not_found(),
+            _ => false,
+        }
+    }
+
+    /// Whether the server asked us to slow down
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            Error::RateLimited { .. } => true,
+            Error::RetriesExhausted { source, .. } => source.is_rate_limited(),
+            _ => false,
+        }
+    }
+
+    /// HTTP status code of the response that caused the error, if any
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Error::NotFound { .. } => Some(404),
+            Error::PrivateRoute => Some(403),
+            Error::RateLimited { .. } => Some(429),
+            Error::HttpStatus { status, .. } => Some(*status),
+            Error::Http(e) => e.status().map(|s| s.as_u16()),
+            Error::RetriesExhausted { source, .. } => source.status(),
+            _ => None,
+        }
+    }
+
+    /// How long the server asked us to wait before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::RateLimited { retry_after, .. } | Error::HttpStatus { retry_after, .. } => *retry_after,
+            Error::RetriesExhausted { source, .. } => source.retry_after(),
+            _ => None,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classification() {
+        let not_found = Error::NotFound { url: "https://letterboxd.com/film/x/".to_string() };
+        assert!(not_found.is_not_found());
+        assert!(!not_found.is_retryable());
+        assert_eq!(not_found.status(), Some(404));
+
+        let rate_limited = Error::RateLimited {
+            url: "https://letterboxd.com/".to_string(),
+            retry_after: Some(Duration::from_secs(30)),
+        };
+        assert!(rate_limited.is_retryable());
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(30)));
+
+        let unavailable = Error::HttpStatus {
+            status: 503,
+            url: String::new(),
+            retry_after: Some(Duration::from_secs(5)),
+        };
+        assert!(unavailable.is_retryable());
+        assert_eq!(unavailable.retry_after(), Some(Duration::from_secs(5)));
+        assert!(!Error::HttpStatus { status: 400, url: String::new(), retry_after: None }.is_retryable());
+    }
+
+    #[test]
+    fn test_parse_error_names_the_element() {
+        let error = Error::parse("https://letterboxd.com/film/x/", "[data-film-id]", "film id");
+
+        assert!(matches!(&error, Error::Parse { selector, .. } if selector == "[data-film-id]"));
+        assert_eq!(error.to_string(), "Failed to parse [data-film-id] on https://letterboxd.com/film/x/: film id");
+    }
+
+    #[test]
+    fn test_retries_exhausted_exposes_source() {
+        let error = Error::RetriesExhausted {
+            url: "https://letterboxd.com/".to_string(),
+            attempts: 3,
+            source: Box::new(Error::NotFound { url: "https://letterboxd.com/".to_string() }),
+        };
+
+        assert!(error.is_not_found());
+        assert_eq!(error.status(), Some(404));
+        assert!(!error.is_retryable());
+    }
+}
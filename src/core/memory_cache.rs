@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+use crate::core::Result;
+
+/// Bounded in-memory LRU cache shared by every clone of a [`Client`](crate::Client).
+///
+/// Concurrent lookups of the same missing key are de-duplicated: the first
+/// caller runs the loader and the others wait for its result. A failed load
+/// is not cached, so the next caller tries again.
+pub struct MemoryCache<K, V> {
+    capacity: usize,
+    state: Mutex<State<K, V>>,
+}
+
+struct State<K, V> {
+    entries: HashMap<K, Slot<V>>,
+    /// Incremented on every access; the entry with the lowest `last_used` is evicted first
+    clock: u64,
+}
+
+struct Slot<V> {
+    cell: Arc<OnceCell<V>>,
+    last_used: u64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> MemoryCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(State {
+                entries: HashMap::new(),
+                clock: 0,
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of cached values, not counting loads still in flight
+    pub fn len(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.entries.values().filter(|slot| slot.cell.initialized()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Cached value for `key`, marking it as recently used
+    pub fn get(&self, key: &K) -> Option<V> {
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let clock = state.clock;
+
+        let slot = state.entries.get_mut(key)?;
+        slot.last_used = clock;
+        slot.cell.get().cloned()
+    }
+
+    /// Cache `value` under `key`, replacing any previous value
+    pub fn insert(&self, key: K, value: V) {
+        let cell = Arc::new(OnceCell::new_with(Some(value)));
+        let mut state = self.state.lock().unwrap();
+        state.clock += 1;
+        let last_used = state.clock;
+
+        state.entries.insert(key, Slot { cell, last_used });
+        self.evict(&mut state);
+    }
+
+    /// Cached value for `key`, or the result of `load` which is cached on success
+    pub async fn get_or_try_insert_with<F, Fut>(&self, key: K, load: F) -> Result<V>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V>>,
+    {
+        let cell = {
+            let mut state = self.state.lock().unwrap();
+            state.clock += 1;
+            let last_used = state.clock;
+
+            let slot = state.entries.entry(key.clone()).or_insert_with(|| Slot {
+                cell: Arc::new(OnceCell::new()),
+                last_used,
+            });
+            slot.last_used = last_used;
+            let cell = slot.cell.clone();
+
+            self.evict(&mut state);
+            cell
+        };
+
+        let result = cell.get_or_try_init(load).await.cloned();
+
+        if result.is_err() {
+            // Drop the empty slot unless another caller has since filled or replaced it
+            let mut state = self.state.lock().unwrap();
+            if state.entries.get(&key).is_some_and(|slot| Arc::ptr_eq(&slot.cell, &cell) && !cell.initialized()) {
+                state.entries.remove(&key);
+            }
+        }
+        result
+    }
+
+    pub fn remove(&self, key: &K) {
+        self.state.lock().unwrap().entries.remove(key);
+    }
+
+    pub fn clear(&self) {
+        self.state.lock().unwrap().entries.clear();
+    }
+
+    fn evict(&self, state: &mut State<K, V>) {
+        while state.entries.len() > self.capacity {
+            let oldest = state.entries
+                .iter()
+                .min_by_key(|(_, slot)| slot.last_used)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(key) => state.entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+impl<K, V> std::fmt::Debug for MemoryCache<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryCache")
+            .field("capacity", &self.capacity)
+            .field("entries", &self.state.lock().map(|s| s.entries.len()).unwrap_or(0))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use crate::core::Error;
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let cache = MemoryCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+
+        cache.insert("c", 3);
+
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"c"), Some(3));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_loads_are_deduplicated() {
+        let cache = MemoryCache::new(10);
+        let loads = AtomicUsize::new(0);
+        let load = || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            Ok("value")
+        };
+
+        let (a, b) = tokio::join!(
+            cache.get_or_try_insert_with("key", load),
+            cache.get_or_try_insert_with("key", load),
+        );
+
+        assert_eq!((a.unwrap(), b.unwrap()), ("value", "value"));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_loads_are_not_cached() {
+        let cache: MemoryCache<&str, u32> = MemoryCache::new(10);
+
+        let failed = cache.get_or_try_insert_with("key", || async { Err(Error::InvalidInput("boom".to_string())) }).await;
+        assert!(failed.is_err());
+        assert!(cache.is_empty());
+
+        let loaded = cache.get_or_try_insert_with("key", || async { Ok(7) }).await;
+        assert_eq!(loaded.unwrap(), 7);
+    }
+}
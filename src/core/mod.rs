@@ -1,6 +1,22 @@
+pub mod cache;
+pub mod cassette;
 pub mod client;
+pub mod cookies;
 pub mod error;
+pub mod memory_cache;
 pub mod constants;
+pub mod paginator;
+pub mod rate_limit;
+pub mod retry;
+pub mod transport;
 
-pub use client::Client;
+pub use cache::HttpCache;
+pub use cassette::CassetteMode;
+pub use client::{Client, ClientBuilder, RedirectPolicy};
+pub use cookies::CookieJar;
 pub use error::{Error, Result};
+pub use memory_cache::MemoryCache;
+pub use paginator::Paginator;
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
+pub use transport::{HttpRequest, HttpResponse, Method, MockTransport, ReqwestTransport, Transport};
@@ -0,0 +1,193 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use futures::future::BoxFuture;
+use futures::stream::{FuturesOrdered, Stream, StreamExt};
+use scraper::{Html, Selector};
+use crate::core::{Client, Result};
+use crate::utils::transform::add_page_to_url;
+
+/// Link to the next page, present on every page but the last
+const NEXT_PAGE_SELECTOR: &str = ".paginate-nextprev a.next, .pagination a.next";
+
+type PageUrl = Arc<dyn Fn(u32) -> String + Send + Sync>;
+type PageParser<T> = Arc<dyn Fn(&Html, &str) -> Result<Vec<T>> + Send + Sync>;
+/// A page's number, and its items and whether it links to a next page
+type PageFetch<T> = BoxFuture<'static, (u32, Result<(Vec<T>, bool)>)>;
+
+/// Items of a paged listing, fetched page by page as the stream is polled.
+///
+/// The last page is the first one without a "next" link, so pages of any
+/// size work. The stream ends after the first error. Use the
+/// [`StreamExt`](futures::StreamExt) and [`TryStreamExt`](futures::TryStreamExt)
+/// combinators to consume it:
+///
+/// ```no_run
+/// use futures::{StreamExt, TryStreamExt};
+/// use rustboxd::pages::UserDiary;
+///
+/// # async fn run() -> rustboxd::Result<()> {
+/// let diary = UserDiary::new("username");
+///
+/// // The ten most recent entries, without fetching the rest of the diary
+/// let recent: Vec<_> = diary.entries(None, None, None).take(10).try_collect().await?;
+///
+/// // Everything from page 5 on, fetching up to two pages ahead
+/// let mut older = diary.entries(None, None, None).start_page(5).prefetch(2);
+/// while let Some(entry) = older.next().await {
+///     println!("{}", entry?.film_title);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginator<T> {
+    client: Client,
+    page_url: PageUrl,
+    parse: PageParser<T>,
+    prefetch: usize,
+    /// Next page to request
+    next_page: u32,
+    /// Page the buffered items came from
+    page: Option<u32>,
+    last_page_seen: bool,
+    failed: bool,
+    items: VecDeque<T>,
+    fetches: FuturesOrdered<PageFetch<T>>,
+}
+
+impl<T: Send + 'static> Paginator<T> {
+    /// A paginator over the pages `page_url` builds, starting at page 1.
+    ///
+    /// `parse` gets each page and its URL and returns the items on it.
+    pub fn new<U, P>(client: &Client, page_url: U, parse: P) -> Self
+    where
+        U: Fn(u32) -> String + Send + Sync + 'static,
+        P: Fn(&Html, &str) -> Result<Vec<T>> + Send + Sync + 'static,
+    {
+        Self {
+            client: client.clone(),
+            page_url: Arc::new(page_url),
+            parse: Arc::new(parse),
+            prefetch: 0,
+            next_page: 1,
+            page: None,
+            last_page_seen: false,
+            failed: false,
+            items: VecDeque::new(),
+            fetches: FuturesOrdered::new(),
+        }
+    }
+
+    /// A paginator over `url` and its `page/N/` pages
+    pub fn for_url<P>(client: &Client, url: &str, parse: P) -> Self
+    where
+        P: Fn(&Html, &str) -> Result<Vec<T>> + Send + Sync + 'static,
+    {
+        let url = url.to_string();
+        Self::new(client, move |page| page_url(&url, page), parse)
+    }
+
+    /// Start at `page` instead of page 1, e.g. to resume from [`Paginator::page`]
+    pub fn start_page(mut self, page: u32) -> Self {
+        self.next_page = page.max(1);
+        self
+    }
+
+    /// Request up to `pages` pages ahead of the one being read.
+    ///
+    /// Pages ahead are requested before it is known whether they exist;
+    /// any past the last page are discarded. The client's rate limit
+    /// still applies to every request.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+
+    /// Page the most recently returned item came from
+    pub fn page(&self) -> Option<u32> {
+        self.page
+    }
+
+    fn request_pages(&mut self) {
+        while !self.last_page_seen && self.fetches.len() <= self.prefetch {
+            let page = self.next_page;
+            self.next_page += 1;
+
+            let client = self.client.clone();
+            let url = (self.page_url)(page);
+            let parse = self.parse.clone();
+            self.fetches.push_back(Box::pin(async move {
+                (page, fetch_page(&client, &url, parse.as_ref()).await)
+            }));
+        }
+    }
+}
+
+/// URL of a page of a listing; the first page is the listing's own URL
+pub(crate) fn page_url(url: &str, page: u32) -> String {
+    if page > 1 { add_page_to_url(url, page) } else { url.to_string() }
+}
+
+/// Whether a page links to a next one
+pub(crate) fn has_next_page(dom: &Html) -> bool {
+    dom.select(&Selector::parse(NEXT_PAGE_SELECTOR).unwrap()).next().is_some()
+}
+
+async fn fetch_page<T>(client: &Client, url: &str, parse: &(dyn Fn(&Html, &str) -> Result<Vec<T>> + Send + Sync)) -> Result<(Vec<T>, bool)> {
+    let dom = client.get_page(url).await?;
+    let items = parse(&dom, url)?;
+    Ok((items, has_next_page(&dom)))
+}
+
+impl<T: Send + 'static> Stream for Paginator<T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(item) = this.items.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.failed {
+                return Poll::Ready(None);
+            }
+
+            this.request_pages();
+            match this.fetches.poll_next_unpin(cx) {
+                Poll::Ready(Some((page, Ok((items, has_next))))) => {
+                    this.page = Some(page);
+                    this.items.extend(items);
+                    if !has_next {
+                        // Anything requested past the last page is discarded
+                        this.last_page_seen = true;
+                        this.fetches = FuturesOrdered::new();
+                    }
+                }
+                Poll::Ready(Some((page, Err(e)))) => {
+                    this.page = Some(page);
+                    this.failed = true;
+                    this.fetches = FuturesOrdered::new();
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+// Nothing is pinned in place; the pending fetches are boxed
+impl<T> Unpin for Paginator<T> {}
+
+impl<T> std::fmt::Debug for Paginator<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Paginator")
+            .field("next_page", &self.next_page)
+            .field("page", &self.page)
+            .field("prefetch", &self.prefetch)
+            .field("last_page_seen", &self.last_page_seen)
+            .finish_non_exhaustive()
+    }
+}
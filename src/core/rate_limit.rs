@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// Throttles requests made through a [`Client`](crate::Client).
+///
+/// Combines a token bucket (requests per second, with an optional burst) and
+/// a cap on the number of requests in flight to the same host. The limiter is
+/// shared by every clone of the client it was built for.
+#[derive(Debug)]
+pub struct RateLimiter {
+    bucket: Option<Mutex<TokenBucket>>,
+    max_in_flight: Option<usize>,
+    hosts: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+/// Held for the duration of a request; releases the in-flight slot on drop.
+#[derive(Debug)]
+pub struct RateLimitPermit {
+    _slot: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimiter {
+    /// Create a limiter. `requests_per_second` enables the token bucket and
+    /// `max_in_flight` caps concurrent requests per host; either may be `None`.
+    pub fn new(requests_per_second: Option<f64>, burst: u32, max_in_flight: Option<usize>) -> Self {
+        let bucket = requests_per_second.map(|rate| {
+            let capacity = f64::from(burst.max(1));
+            Mutex::new(TokenBucket {
+                capacity,
+                tokens: capacity,
+                refill_per_sec: rate,
+                last_refill: Instant::now(),
+            })
+        });
+
+        Self {
+            bucket,
+            max_in_flight,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait until a request to `url` may be sent
+    pub async fn acquire(&self, url: &str) -> RateLimitPermit {
+        // Take the in-flight slot first so that queued requests do not use up tokens
+        let slot = match self.host_semaphore(url) {
+            Some(semaphore) => semaphore.acquire_owned().await.ok(),
+            None => None,
+        };
+
+        if let Some(wait) = self.reserve_token() {
+            tokio::time::sleep(wait).await;
+        }
+
+        RateLimitPermit { _slot: slot }
+    }
+
+    fn host_semaphore(&self, url: &str) -> Option<Arc<Semaphore>> {
+        let max_in_flight = self.max_in_flight?;
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_default();
+
+        let mut hosts = self.hosts.lock().unwrap();
+        Some(hosts
+            .entry(host)
+            .or_insert_with(|| Arc::new(Semaphore::new(max_in_flight)))
+            .clone())
+    }
+
+    /// Take a token, returning how long to wait if the bucket was empty.
+    ///
+    /// The token is reserved immediately (the balance may go negative) so
+    /// concurrent callers queue up behind each other instead of racing.
+    fn reserve_token(&self) -> Option<Duration> {
+        let mut bucket = self.bucket.as_ref()?.lock().unwrap();
+
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * bucket.refill_per_sec).min(bucket.capacity);
+        bucket.last_refill = now;
+
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            None
+        } else {
+            Some(Duration::from_secs_f64(-bucket.tokens / bucket.refill_per_sec))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_token_bucket_spaces_requests() {
+        let limiter = RateLimiter::new(Some(20.0), 1, None);
+        let start = std::time::Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire("https://letterboxd.com/").await;
+        }
+
+        // First request uses the burst token, the next two wait 50ms each
+        assert!(start.elapsed() >= Duration::from_millis(90));
+    }
+
+    #[tokio::test]
+    async fn test_burst_allows_immediate_requests() {
+        let limiter = RateLimiter::new(Some(1.0), 3, None);
+        let start = std::time::Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire("https://letterboxd.com/").await;
+        }
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_in_flight_cap_is_per_host() {
+        let limiter = RateLimiter::new(None, 1, Some(1));
+        let _held = limiter.acquire("https://letterboxd.com/film/a/").await;
+
+        let same_host = tokio::time::timeout(
+            Duration::from_millis(50),
+            limiter.acquire("https://letterboxd.com/film/b/"),
+        ).await;
+        assert!(same_host.is_err());
+
+        let other_host = tokio::time::timeout(
+            Duration::from_millis(50),
+            limiter.acquire("https://boxd.it/abc"),
+        ).await;
+        assert!(other_host.is_ok());
+    }
+}
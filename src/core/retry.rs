@@ -0,0 +1,169 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// When and how often a failed GET request is retried.
+///
+/// Only transient failures are retried: connection errors, timeouts and the
+/// HTTP statuses 408, 429, 500, 502, 503 and 504. The delay between attempts
+/// grows exponentially from `base_delay` up to `max_delay`, with full jitter
+/// when `jitter` is set. A `Retry-After` header on the response takes
+/// precedence over the computed delay, but is capped at `max_delay` too.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub jitter: bool,
+    pub respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            respect_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy with the given number of attempts
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Self::default()
+        }
+    }
+
+    /// Never retry
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    pub fn respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Delay before the attempt following `attempt` (1-based)
+    pub fn delay_for(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        if let Some(retry_after) = retry_after.filter(|_| self.respect_retry_after) {
+            return retry_after.min(self.max_delay);
+        }
+
+        let exponent = attempt.saturating_sub(1).min(31);
+        let backoff = self.base_delay
+            .saturating_mul(1u32 << exponent)
+            .min(self.max_delay);
+
+        if self.jitter {
+            backoff.mul_f64(random_unit())
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Whether an HTTP status is worth retrying
+pub fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 408 | 429 | 500 | 502 | 503 | 504)
+}
+
+/// Parse a `Retry-After` header value, either delay-seconds or an HTTP date
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Random number in `[0, 1)`, good enough for spreading out retries
+fn random_unit() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default());
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_backoff_without_jitter() {
+        let policy = RetryPolicy::new(5)
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(350))
+            .jitter(false);
+
+        assert_eq!(policy.delay_for(1, None), Duration::from_millis(100));
+        assert_eq!(policy.delay_for(2, None), Duration::from_millis(200));
+        assert_eq!(policy.delay_for(3, None), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn test_jitter_stays_below_backoff() {
+        let policy = RetryPolicy::new(5).base_delay(Duration::from_millis(100));
+
+        for attempt in 1..5 {
+            assert!(policy.delay_for(attempt, None) <= Duration::from_millis(100 << (attempt - 1)));
+        }
+    }
+
+    #[test]
+    fn test_retry_after_takes_precedence() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay_for(1, Some(Duration::from_secs(7))), Duration::from_secs(7));
+
+        let ignoring = policy.respect_retry_after(false).jitter(false);
+        assert_eq!(ignoring.delay_for(1, Some(Duration::from_secs(7))), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_retry_after_is_capped_at_max_delay() {
+        let policy = RetryPolicy::default().max_delay(Duration::from_secs(10));
+        assert_eq!(policy.delay_for(1, Some(Duration::from_secs(86400))), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable_status(429));
+        assert!(is_retryable_status(503));
+        assert!(!is_retryable_status(404));
+        assert!(!is_retryable_status(403));
+    }
+}
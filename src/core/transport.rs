@@ -0,0 +1,253 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use crate::core::{Error, Result};
+
+/// HTTP method of an [`HttpRequest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+/// A request handed to a [`Transport`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpRequest {
+    pub method: Method,
+    pub url: String,
+    /// Headers for this request only, on top of the transport's defaults
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl HttpRequest {
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: Method::Get,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// A POST submitting `fields` as `application/x-www-form-urlencoded`, like an HTML form
+    pub fn post_form(url: &str, fields: &[(&str, &str)]) -> Self {
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(fields)
+            .finish();
+
+        Self {
+            method: Method::Post,
+            url: url.to_string(),
+            headers: vec![("Content-Type".to_string(), "application/x-www-form-urlencoded".to_string())],
+            body: Some(body),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// A response returned by a [`Transport`], whatever its status.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpResponse {
+    pub url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl HttpResponse {
+    pub fn new(url: &str, status: u16, body: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    /// A `200 OK` response with an HTML body
+    pub fn ok(url: &str, body: &str) -> Self {
+        Self::new(url, 200, body)
+            .with_header("content-type", "text/html; charset=utf-8")
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// First value of a header, matched case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Sends requests on behalf of a [`Client`](crate::Client).
+///
+/// Rate limiting, retries and status handling all happen in the client, so
+/// an implementation only has to perform a single request. Failures to get
+/// any response at all should be reported as [`Error::Connection`] or
+/// [`Error::Timeout`] so the client can retry them.
+#[async_trait]
+pub trait Transport: Send + Sync + std::fmt::Debug {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse>;
+}
+
+#[async_trait]
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        (**self).execute(request).await
+    }
+}
+
+/// The default transport, backed by `reqwest`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = match request.method {
+            Method::Get => self.client.get(&request.url),
+            Method::Post => self.client.post(&request.url),
+        };
+        for (name, value) in &request.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+        if let Some(body) = &request.body {
+            builder = builder.body(body.clone());
+        }
+
+        let response = builder
+            .send()
+            .await
+            .map_err(|e| request_error(&request.url, e))?;
+
+        let url = response.url().to_string();
+        let status = response.status().as_u16();
+        let headers = response.headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| request_error(&request.url, e))?;
+
+        Ok(HttpResponse { url, status, headers, body })
+    }
+}
+
+/// Classify a transport-level failure
+fn request_error(url: &str, e: reqwest::Error) -> Error {
+    let url = url.to_string();
+    if e.is_timeout() {
+        Error::Timeout { url }
+    } else if e.is_connect() || e.is_request() || e.is_body() {
+        Error::Connection { url, message: e.to_string() }
+    } else {
+        Error::PageLoad { url, message: e.to_string() }
+    }
+}
+
+/// In-memory transport serving canned responses, for offline tests.
+///
+/// URLs are matched ignoring a trailing slash. Unknown URLs get a 404.
+/// When several responses are registered for one URL they are served in
+/// order, and the last one is repeated.
+///
+/// ```
+/// use std::sync::Arc;
+/// use rustboxd::{Client, core::transport::MockTransport};
+///
+/// let transport = Arc::new(MockTransport::new()
+///     .with_page("https://letterboxd.com/film/alien/", "<h1 class=\"headline-1\">Alien</h1>"));
+/// let client = Client::builder().transport(transport.clone()).build().unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct MockTransport {
+    responses: Mutex<HashMap<String, VecDeque<HttpResponse>>>,
+    requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl MockTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `html` with status 200 for `url`
+    pub fn with_page(self, url: &str, html: &str) -> Self {
+        self.with_response(url, HttpResponse::ok(url, html))
+    }
+
+    /// Queue a response for `url`
+    pub fn with_response(self, url: &str, response: HttpResponse) -> Self {
+        self.insert_response(url, response);
+        self
+    }
+
+    pub fn insert_page(&self, url: &str, html: &str) {
+        self.insert_response(url, HttpResponse::ok(url, html));
+    }
+
+    pub fn insert_response(&self, url: &str, response: HttpResponse) {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(Self::key(url))
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Every request received so far, in order
+    pub fn requests(&self) -> Vec<HttpRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Number of requests received for `url`
+    pub fn request_count(&self, url: &str) -> usize {
+        let key = Self::key(url);
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| Self::key(&r.url) == key)
+            .count()
+    }
+
+    fn key(url: &str) -> String {
+        url.trim_end_matches('/').to_string()
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn execute(&self, request: HttpRequest) -> Result<HttpResponse> {
+        self.requests.lock().unwrap().push(request.clone());
+
+        let mut responses = self.responses.lock().unwrap();
+        let response = match responses.get_mut(&Self::key(&request.url)) {
+            Some(queue) if queue.len() > 1 => queue.pop_front(),
+            Some(queue) => queue.front().cloned(),
+            None => None,
+        };
+
+        Ok(response.unwrap_or_else(|| HttpResponse::new(&request.url, 404, "Not Found")))
+    }
+}
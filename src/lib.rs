@@ -6,6 +6,7 @@
 pub mod core;
 pub mod models;
 pub mod pages;
+pub mod session;
 pub mod utils;
 
 // Re-export main types
@@ -14,7 +15,9 @@ pub use models::{
     FavoriteMovie, UserRecent, DiaryData, DiaryEntry,
     WatchlistMovie, DiaryMovieEntry
 };
-pub use core::{Error, Result, Client};
+pub use core::{Error, Result, Client, ClientBuilder, Paginator};
+pub use session::Session;
 
 pub use chrono;
+pub use futures;
 pub use serde_json;
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::core::{Client, Error, Result};
+use futures::{StreamExt, TryStreamExt};
+use crate::core::{Client, Error, Paginator, Result};
+use crate::utils::transform::{add_page_to_url, build_letterboxd_url};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Films {
@@ -25,11 +27,15 @@ pub struct FilmEntry {
 }
 
 impl Films {
-    pub async fn new(url: &str) -> Result<Self> {
-        let client = Client::new();
+    /// The first `limit` films at a films URL; use [`Films::entries`] to page through more
+    pub async fn new(url: &str, limit: usize) -> Result<Self> {
+        Self::new_with_client(&Client::new(), url, limit).await
+    }
+
+    pub async fn new_with_client(client: &Client, url: &str, limit: usize) -> Result<Self> {
         let ajax_url = Self::get_ajax_url(url);
         
-        let movies = Self::scrape_movies(&client, &ajax_url, url).await?;
+        let movies = Self::scrape_movies(client, url, limit).await?;
         let count = movies.len();
 
         Ok(Films {
@@ -51,95 +57,86 @@ impl Films {
         }
     }
 
-    async fn scrape_movies(client: &Client, ajax_url: &str, original_url: &str) -> Result<HashMap<String, FilmEntry>> {
-        let mut movies = HashMap::new();
-        let mut page = 1;
-        
-        const VERTICAL_MAX: usize = 100; // 20 * 5 pages
-        const HORIZONTAL_MAX: usize = 72; // 12 * 6 pages
-
-        loop {
-            let page_url = format!("{}/page/{}", ajax_url, page);
-            let dom = client.get_page(&page_url).await?;
-            
-            let new_movies = if original_url.contains("/films/") {
-                Self::extract_horizontal_movies(&dom)?
-            } else if original_url.contains("/film/") {
-                Self::extract_vertical_movies(&dom)?
-            } else {
-                HashMap::new()
-            };
-
-            let new_count = new_movies.len();
-            movies.extend(new_movies);
-
-            // Check if we should continue pagination
-            let max_per_page = if original_url.contains("/films/") {
-                HORIZONTAL_MAX
-            } else {
-                VERTICAL_MAX
-            };
-
-            if new_count < max_per_page || movies.len() >= 1000 {
-                break;
-            }
-
-            page += 1;
-        }
+    /// Stream of the films at a films URL, page by page
+    pub fn entries(client: &Client, url: &str) -> Paginator<FilmEntry> {
+        let ajax_url = Self::get_ajax_url(url);
+        let original_url = url.to_string();
+        let base_url = client.base_url().to_string();
+
+        Paginator::new(
+            client,
+            move |page| add_page_to_url(&ajax_url, page),
+            move |dom, page_url| {
+                let movies = if original_url.contains("/films/") {
+                    Self::extract_horizontal_movies(dom, &base_url, page_url)?
+                } else if original_url.contains("/film/") {
+                    Self::extract_vertical_movies(dom, &base_url, page_url)?
+                } else {
+                    Vec::new()
+                };
+                Ok(movies)
+            },
+        )
+    }
 
-        Ok(movies)
+    async fn scrape_movies(client: &Client, original_url: &str, limit: usize) -> Result<HashMap<String, FilmEntry>> {
+        Self::entries(client, original_url)
+            .take(limit)
+            .map_ok(|film| (film.slug.clone(), film))
+            .try_collect()
+            .await
     }
 
-    fn extract_horizontal_movies(dom: &scraper::Html) -> Result<HashMap<String, FilmEntry>> {
+    fn extract_horizontal_movies(dom: &scraper::Html, base_url: &str, page_url: &str) -> Result<Vec<FilmEntry>> {
         use scraper::Selector;
         
-        let mut movies = HashMap::new();
+        let mut movies = Vec::new();
         let film_selector = Selector::parse(".poster-container").unwrap();
         
         for element in dom.select(&film_selector) {
-            if let Ok(film) = Self::parse_horizontal_film(&element) {
-                movies.insert(film.slug.clone(), film);
+            if let Ok(film) = Self::parse_horizontal_film(&element, base_url, page_url) {
+                movies.push(film);
             }
         }
 
         Ok(movies)
     }
 
-    fn extract_vertical_movies(dom: &scraper::Html) -> Result<HashMap<String, FilmEntry>> {
+    fn extract_vertical_movies(dom: &scraper::Html, base_url: &str, page_url: &str) -> Result<Vec<FilmEntry>> {
         use scraper::Selector;
         
-        let mut movies = HashMap::new();
+        let mut movies = Vec::new();
         let film_selector = Selector::parse(".film-detail").unwrap();
         
         for element in dom.select(&film_selector) {
-            if let Ok(film) = Self::parse_vertical_film(&element) {
-                movies.insert(film.slug.clone(), film);
+            if let Ok(film) = Self::parse_vertical_film(&element, base_url, page_url) {
+                movies.push(film);
             }
         }
 
         Ok(movies)
     }
 
-    fn parse_horizontal_film(element: &scraper::ElementRef) -> Result<FilmEntry> {
+    fn parse_horizontal_film(element: &scraper::ElementRef, base_url: &str, page_url: &str) -> Result<FilmEntry> {
         use scraper::Selector;
         
         let img_selector = Selector::parse("img").unwrap();
         let link_selector = Selector::parse("a").unwrap();
         
         let img_element = element.select(&img_selector).next()
-            .ok_or_else(|| Error::Parse("Film image not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-container img", "film poster"))?;
         
         let link_element = element.select(&link_selector).next()
-            .ok_or_else(|| Error::Parse("Film link not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-container a", "film link"))?;
         
         let title = img_element.value().attr("alt")
-            .ok_or_else(|| Error::Parse("Film title not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-container img[alt]", "film title"))?;
         
         let href = link_element.value().attr("href")
-            .ok_or_else(|| Error::Parse("Film href not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-container a[href]", "film URL"))?;
         
         let slug = href.trim_start_matches("/film/").trim_end_matches("/").to_string();
-        let url = format!("https://letterboxd.com{}", href);
+        let url = build_letterboxd_url(base_url, href);
         
         let poster = img_element.value().attr("src").map(|s| s.to_string());
 
@@ -157,7 +154,7 @@ impl Films {
         })
     }
 
-    fn parse_vertical_film(element: &scraper::ElementRef) -> Result<FilmEntry> {
+    fn parse_vertical_film(element: &scraper::ElementRef, base_url: &str, page_url: &str) -> Result<FilmEntry> {
         use scraper::Selector;
         
         let title_selector = Selector::parse(".film-title a").unwrap();
@@ -165,14 +162,14 @@ impl Films {
         let poster_selector = Selector::parse(".film-poster img").unwrap();
         
         let title_element = element.select(&title_selector).next()
-            .ok_or_else(|| Error::Parse("Film title not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".film-detail .film-title a", "film title"))?;
         
         let title = title_element.inner_html();
         let href = title_element.value().attr("href")
-            .ok_or_else(|| Error::Parse("Film URL not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".film-detail .film-title a[href]", "film URL"))?;
         
         let slug = href.trim_start_matches("/film/").trim_end_matches("/").to_string();
-        let url = format!("https://letterboxd.com{}", href);
+        let url = build_letterboxd_url(base_url, href);
         
         let year = element.select(&year_selector)
             .next()
@@ -205,7 +202,7 @@ impl Films {
 
     pub fn filter_by_rating(&self, min_rating: f32) -> Vec<&FilmEntry> {
         self.movies.values()
-            .filter(|film| film.rating.map_or(false, |r| r >= min_rating))
+            .filter(|film| film.rating.is_some_and(|r| r >= min_rating))
             .collect()
     }
 
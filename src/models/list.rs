@@ -1,9 +1,17 @@
+use futures::TryStreamExt;
+use scraper::{ElementRef, Selector};
 use serde::{Deserialize, Serialize};
-use crate::core::{Client, Error, Result, constants::DOMAIN};
+use crate::core::{Client, Error, Paginator, Result};
+use crate::core::paginator::has_next_page;
+use crate::utils::parser::clean_text;
+use crate::utils::transform::{build_letterboxd_url, build_list_url};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct List {
+    /// Letterboxd's numeric id for the list, needed to edit or delete it
+    pub list_id: Option<u64>,
     pub title: String,
+    /// Description as plain text, with paragraphs separated by blank lines
     pub description: Option<String>,
     pub author: String,
     pub slug: String,
@@ -12,14 +20,20 @@ pub struct List {
     pub likes: u32,
     pub comments: u32,
     pub is_ranked: bool,
+    /// Whether anyone can see the list; only its owner sees a private one
+    pub is_public: bool,
+    /// Every film in the list, from all of its pages
     pub films: Vec<ListFilm>,
     pub tags: Vec<String>,
     pub created_date: Option<String>,
     pub updated_date: Option<String>,
+    #[serde(skip)]
+    client: Client,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListFilm {
+    pub film_id: Option<u64>,
     pub title: String,
     pub year: Option<i32>,
     pub slug: String,
@@ -27,59 +41,82 @@ pub struct ListFilm {
     pub poster: Option<String>,
     pub director: Option<String>,
     pub position: Option<u32>,
+    /// The author's notes on the entry, as plain text
     pub notes: Option<String>,
 }
 
 impl List {
     pub async fn new(author: &str, slug: &str) -> Result<Self> {
-        let client = Client::new();
-        let url = format!("{}/{}/list/{}", DOMAIN, author, slug);
-        
-        let dom = client.get_page(&url).await?;
-        let list = Self::parse_list_data(&dom, author, slug, &url)?;
+        Self::new_with_client(&Client::new(), author, slug).await
+    }
+
+    pub async fn new_with_client(client: &Client, author: &str, slug: &str) -> Result<Self> {
+        let url = build_list_url(client.base_url(), author, slug);
         
-        Ok(list)
+        Self::load(client, author, slug, &url).await
     }
 
     pub async fn from_url(url: &str) -> Result<Self> {
-        let client = Client::new();
-        let dom = client.get_page(url).await?;
-        
+        Self::from_url_with_client(&Client::new(), url).await
+    }
+
+    pub async fn from_url_with_client(client: &Client, url: &str) -> Result<Self> {
         // Extract author and slug from URL
         let url_parts: Vec<&str> = url.split('/').collect();
         if url_parts.len() < 6 {
-            return Err(Error::Parse("Invalid list URL format".to_string()));
+            return Err(Error::InvalidInput(format!("Invalid list URL: {}", url)));
         }
         
         let author = url_parts[3];
         let slug = url_parts[5];
         
-        let list = Self::parse_list_data(&dom, author, slug, url)?;
-        
+        Self::load(client, author, slug, url).await
+    }
+
+    /// Fetch the list and the films on every page of it
+    async fn load(client: &Client, author: &str, slug: &str, url: &str) -> Result<Self> {
+        let dom = client.get_page(url).await?;
+        let mut list = Self::parse_list_data(&dom, client, author, slug, url)?;
+
+        if has_next_page(&dom) {
+            let base_url = client.base_url().to_string();
+            let rest: Vec<ListFilm> = Paginator::for_url(client, url, move |dom, page_url| {
+                Ok(Self::parse_list_films(dom, &base_url, page_url))
+            })
+                .start_page(2)
+                .try_collect()
+                .await?;
+            list.films.extend(rest);
+        }
+
+        for (index, film) in list.films.iter_mut().enumerate() {
+            film.position = Some(index as u32 + 1);
+        }
         Ok(list)
     }
 
-    fn parse_list_data(dom: &scraper::Html, author: &str, slug: &str, url: &str) -> Result<Self> {
-        use scraper::Selector;
-        
+    fn parse_list_data(dom: &scraper::Html, client: &Client, author: &str, slug: &str, url: &str) -> Result<Self> {
         let title_selector = Selector::parse("h1.list-title").unwrap();
         let description_selector = Selector::parse(".list-description").unwrap();
         let stats_selector = Selector::parse(".list-stats li").unwrap();
-        let film_selector = Selector::parse(".poster-list li").unwrap();
         let tags_selector = Selector::parse(".list-tags a").unwrap();
+        let list_id_selector = Selector::parse("[data-film-list-id]").unwrap();
+        let ranked_selector = Selector::parse(".numbered-list-item").unwrap();
+        let private_selector = Selector::parse(".list-header .icon-private").unwrap();
         
         let title = dom.select(&title_selector)
             .next()
-            .map(|el| el.inner_html())
+            .map(|el| clean_text(&el.text().collect::<String>()))
             .unwrap_or_else(|| "Untitled List".to_string());
 
         let description = dom.select(&description_selector)
             .next()
-            .map(|el| el.inner_html());
+            .map(paragraph_text)
+            .filter(|text| !text.is_empty());
 
         let stats: Vec<_> = dom.select(&stats_selector).collect();
-        let film_count = stats.get(0)
-            .and_then(|el| el.inner_html().parse().ok())
+        let film_count = stats.first()
+            .and_then(|el| Self::parse_count_text(&el.inner_html()))
             .unwrap_or(0);
         
         let likes = stats.get(1)
@@ -90,20 +127,23 @@ impl List {
             .and_then(|el| Self::parse_count_text(&el.inner_html()))
             .unwrap_or(0);
 
-        // Parse films
-        let mut films = Vec::new();
-        for (index, element) in dom.select(&film_selector).enumerate() {
-            if let Ok(film) = Self::parse_list_film(&element, index as u32 + 1) {
-                films.push(film);
-            }
-        }
+        let films = Self::parse_list_films(dom, client.base_url(), url);
+
+        let list_id = dom.select(&list_id_selector)
+            .next()
+            .and_then(|el| el.value().attr("data-film-list-id"))
+            .and_then(|id| id.parse().ok());
+
+        let is_ranked = dom.select(&ranked_selector).next().is_some();
+        let is_public = dom.select(&private_selector).next().is_none();
 
         // Parse tags
         let tags: Vec<String> = dom.select(&tags_selector)
-            .map(|el| el.inner_html())
+            .map(|el| clean_text(&el.text().collect::<String>()))
             .collect();
 
         Ok(List {
+            list_id,
             title,
             description,
             author: author.to_string(),
@@ -112,50 +152,66 @@ impl List {
             film_count,
             likes,
             comments,
-            is_ranked: false, // TODO: Detect if list is ranked
+            is_ranked,
+            is_public,
             films,
             tags,
             created_date: None, // TODO: Extract creation date
             updated_date: None, // TODO: Extract update date
+            client: client.clone(),
         })
     }
 
-    fn parse_list_film(element: &scraper::ElementRef, position: u32) -> Result<ListFilm> {
-        use scraper::Selector;
-        
+    /// Films on one page of the list, without their positions
+    fn parse_list_films(dom: &scraper::Html, base_url: &str, page_url: &str) -> Vec<ListFilm> {
+        let film_selector = Selector::parse(".poster-list li").unwrap();
+
+        dom.select(&film_selector)
+            .filter_map(|element| Self::parse_list_film(&element, base_url, page_url).ok())
+            .collect()
+    }
+
+    fn parse_list_film(element: &scraper::ElementRef, base_url: &str, page_url: &str) -> Result<ListFilm> {
         let poster_selector = Selector::parse(".poster").unwrap();
         let img_selector = Selector::parse("img").unwrap();
         let link_selector = Selector::parse("a").unwrap();
+        let notes_selector = Selector::parse(".body-text").unwrap();
         
         let poster_element = element.select(&poster_selector).next()
-            .ok_or_else(|| Error::Parse("Poster element not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-list li .poster", "list entry poster"))?;
         
         let img_element = poster_element.select(&img_selector).next()
-            .ok_or_else(|| Error::Parse("Image element not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-list li .poster img", "list entry image"))?;
         
         let link_element = poster_element.select(&link_selector).next()
-            .ok_or_else(|| Error::Parse("Link element not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-list li .poster a", "list entry link"))?;
         
         let title = img_element.value().attr("alt")
-            .ok_or_else(|| Error::Parse("Film title not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-list li .poster img[alt]", "list entry title"))?;
         
         let href = link_element.value().attr("href")
-            .ok_or_else(|| Error::Parse("Film href not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".poster-list li .poster a[href]", "list entry URL"))?;
         
         let slug = href.trim_start_matches("/film/").trim_end_matches("/").to_string();
-        let url = format!("{}{}", DOMAIN, href);
+        let url = build_letterboxd_url(base_url, href);
         
         let poster = img_element.value().attr("src").map(|s| s.to_string());
+        let film_id = poster_element.value().attr("data-film-id").and_then(|id| id.parse().ok());
+        let notes = element.select(&notes_selector)
+            .next()
+            .map(paragraph_text)
+            .filter(|text| !text.is_empty());
 
         Ok(ListFilm {
+            film_id,
             title: title.to_string(),
             year: None, // TODO: Extract year if available
             slug,
             url,
             poster,
             director: None, // TODO: Extract director if available
-            position: Some(position),
-            notes: None, // TODO: Extract notes if available
+            position: None,
+            notes,
         })
     }
 
@@ -174,16 +230,13 @@ impl List {
     }
 
     pub async fn get_comments(&self) -> Result<Vec<ListComment>> {
-        let client = Client::new();
-        let url = format!("{}/comments/", self.url);
-        let dom = client.get_page(&url).await?;
+        let url = format!("{}/comments/", self.url.trim_end_matches('/'));
+        let dom = self.client.get_page(&url).await?;
         
         Self::parse_comments(&dom)
     }
 
     fn parse_comments(dom: &scraper::Html) -> Result<Vec<ListComment>> {
-        use scraper::Selector;
-        
         let mut comments = Vec::new();
         let comment_selector = Selector::parse(".comment").unwrap();
         
@@ -197,8 +250,6 @@ impl List {
     }
 
     fn parse_comment(element: &scraper::ElementRef) -> Result<ListComment> {
-        use scraper::Selector;
-        
         let author_selector = Selector::parse(".comment-author").unwrap();
         let content_selector = Selector::parse(".comment-content").unwrap();
         let date_selector = Selector::parse(".comment-date").unwrap();
@@ -244,3 +295,18 @@ pub struct ListComment {
     pub date: String,
     pub likes: u32,
 }
+
+/// Text of `element` with one paragraph per `<p>`, separated by blank lines
+fn paragraph_text(element: ElementRef) -> String {
+    let paragraph_selector = Selector::parse("p").unwrap();
+
+    let paragraphs: Vec<String> = element.select(&paragraph_selector)
+        .map(|p| clean_text(&p.text().collect::<String>()))
+        .filter(|text| !text.is_empty())
+        .collect();
+    if paragraphs.is_empty() {
+        clean_text(&element.text().collect::<String>())
+    } else {
+        paragraphs.join("\n\n")
+    }
+}
@@ -3,12 +3,16 @@ pub mod movie;
 pub mod search;
 pub mod films;
 pub mod list;
+pub mod release;
+pub mod review;
 
-pub use user::{User, FavoriteMovie, UserRecent, DiaryData, DiaryEntry};
-pub use movie::Movie;
+pub use user::{User, FavoriteMovie, MemberTier, UserRecent, UserStats, DiaryData, DiaryEntry, AVATAR_SIZES};
+pub use movie::{Crew, CrewRole, FilmMember, FilmStats, Genre, MemberCounts, MemberFilter, Movie, MoviePerson, Nanogenre, SimilarFilm, Theme};
 pub use search::Search;
 pub use films::Films;
 pub use list::List;
+pub use release::{Release, ReleaseType};
+pub use review::{Review, ReviewQuery, ReviewSort};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WatchlistMovie {
@@ -30,4 +34,6 @@ pub struct DiaryMovieEntry {
     pub description: Option<String>,
     pub month: u32,
     pub day: u32,
+    #[serde(default)]
+    pub date: Option<chrono::NaiveDate>,
 }
@@ -1,6 +1,15 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use regex::Regex;
+use chrono::NaiveDate;
+use scraper::{ElementRef, Html, Selector};
+use futures::{StreamExt, TryStreamExt};
+use crate::core::{Client, Error, Paginator, Result};
+use crate::models::release::{parse_releases, Release};
+use crate::models::review::{parse_review, review_listing, Review, ReviewQuery};
+use crate::utils::parser::{clean_text, extract_numeric_text, parse_rating_class, parse_runtime, parse_shorthand, parse_written_date};
+use crate::utils::transform::{build_film_url, build_film_section_url};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Movie {
@@ -9,8 +18,11 @@ pub struct Movie {
     pub movie_id: Option<u64>,
     pub title: String,
     pub original_title: Option<String>,
-    pub runtime: Option<String>,
-    pub rating: Option<String>,
+    pub runtime_minutes: Option<u32>,
+    /// Average member rating out of 5
+    pub average_rating: Option<f32>,
+    /// Number of members who rated the film
+    pub rating_count: Option<u32>,
     pub year: Option<u16>,
     pub tmdb_link: Option<String>,
     pub imdb_link: Option<String>,
@@ -21,10 +33,19 @@ pub struct Movie {
     pub trailer: Option<MovieTrailer>,
     pub alternative_titles: Vec<String>,
     pub details: Option<MovieDetails>,
-    pub genres: Vec<HashMap<String, serde_json::Value>>,
+    pub genres: Vec<Genre>,
+    #[serde(default)]
+    pub themes: Vec<Theme>,
+    /// Narrower themes, e.g. "Humanity and the world around us"
+    #[serde(default)]
+    pub mini_themes: Vec<Theme>,
+    #[serde(default)]
+    pub nanogenres: Vec<Nanogenre>,
     pub cast: Vec<MoviePerson>,
-    pub crew: HashMap<String, Vec<HashMap<String, String>>>,
-    pub popular_reviews: Vec<MovieReview>,
+    pub crew: Crew,
+    pub popular_reviews: Vec<Review>,
+    #[serde(skip)]
+    client: Client,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,7 +68,7 @@ pub struct MovieDetails {
     pub languages: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MoviePerson {
     pub name: String,
     pub role_name: Option<String>,
@@ -55,53 +76,471 @@ pub struct MoviePerson {
     pub url: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MovieReview {
-    pub author: String,
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Genre {
+    pub name: String,
+    pub slug: String,
+    /// Browse page of the genre's films
+    pub url: String,
+}
+
+/// A theme Letterboxd has grouped the film under, e.g. "Dystopian sci-fi"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    pub slug: String,
+    /// Browse page of the theme's films
+    pub url: String,
+}
+
+/// A finer-grained cluster than a theme, e.g. "Virtual reality, hackers, computers"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Nanogenre {
+    pub name: String,
+    pub slug: String,
+    /// Browse page of the nanogenre's films
+    pub url: String,
+}
+
+/// A film Letterboxd lists as similar to another
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimilarFilm {
+    pub name: String,
+    pub slug: String,
+    pub url: String,
+    pub film_id: Option<u64>,
+    pub year: Option<u16>,
+}
+
+/// Which of a film's members [`Movie::watchers`] lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemberFilter {
+    /// Everyone who logged the film as watched
+    #[default]
+    Watched,
+    Liked,
+    /// Members who rated it, with their rating
+    Rated,
+    Reviewed,
+    /// Members with the film on their watchlist
+    Watchlisted,
+    /// Members with the film among their favourites
+    Fans,
+}
+
+impl MemberFilter {
+    /// Section of the film's URL listing these members
+    fn section(self) -> &'static str {
+        match self {
+            MemberFilter::Watched => "members",
+            MemberFilter::Liked => "likes",
+            MemberFilter::Rated => "ratings",
+            MemberFilter::Reviewed => "reviewers",
+            MemberFilter::Watchlisted => "watchlist",
+            MemberFilter::Fans => "fans",
+        }
+    }
+}
+
+/// A member listed by [`Movie::watchers`], with what they did with the film
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilmMember {
+    pub username: String,
+    pub display_name: String,
+    pub url: String,
+    pub avatar: Option<String>,
     pub rating: Option<f32>,
-    pub content: String,
-    pub likes: u32,
-    pub date: String,
+    pub liked: bool,
+    /// Their review of the film, if they wrote one
+    pub review_url: Option<String>,
+}
+
+/// Totals shown in the film's stats bar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberCounts {
+    pub watched: Option<u32>,
+    /// Number of lists the film appears in
+    pub listed: Option<u32>,
+    pub liked: Option<u32>,
+}
+
+/// Ratings and community totals of a film, from [`Movie::get_stats`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FilmStats {
+    /// Weighted average Letterboxd shows, out of 5
+    pub average_rating: Option<f32>,
+    pub rating_count: u32,
+    /// Ratings per half star, from ½ star at index 0 to 5 stars at index 9
+    pub histogram: [u32; 10],
+    /// Members with the film among their favourites
+    pub fans: Option<u32>,
+    pub members: MemberCounts,
+    /// Position in Letterboxd's Top 250 narrative features, if it is in it
+    pub top_250_rank: Option<u32>,
+}
+
+impl FilmStats {
+    /// Star rating counted at each histogram index, 0.5 to 5.0
+    pub fn histogram_rating(index: usize) -> f32 {
+        (index + 1) as f32 / 2.0
+    }
+
+    /// Unweighted mean of the rating histogram
+    pub fn mean_rating(&self) -> Option<f32> {
+        self.bayesian_average(0.0, 0)
+    }
+
+    /// Mean of the histogram pulled towards `prior_mean` as if `prior_weight`
+    /// more ratings of `prior_mean` had been given
+    pub fn bayesian_average(&self, prior_mean: f32, prior_weight: u32) -> Option<f32> {
+        let (sum, count) = self.histogram.iter().enumerate().fold((0.0f64, 0u64), |(sum, count), (index, &ratings)| {
+            (sum + Self::histogram_rating(index) as f64 * ratings as f64, count + ratings as u64)
+        });
+        let count = count + prior_weight as u64;
+        if count == 0 {
+            return None;
+        }
+        Some(((sum + prior_mean as f64 * prior_weight as f64) / count as f64) as f32)
+    }
+}
+
+/// A crew job, named as in the crew's URLs, e.g. `/cinematography/bill-pope/`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum CrewRole {
+    Director,
+    CoDirector,
+    Producer,
+    ExecutiveProducer,
+    Writer,
+    OriginalWriter,
+    Story,
+    Casting,
+    Editor,
+    Cinematography,
+    AssistantDirector,
+    AdditionalDirecting,
+    CameraOperator,
+    AdditionalPhotography,
+    ProductionDesign,
+    ArtDirection,
+    SetDecoration,
+    SpecialEffects,
+    VisualEffects,
+    TitleDesign,
+    Stunts,
+    Choreography,
+    Composer,
+    Songs,
+    Sound,
+    CostumeDesign,
+    Makeup,
+    Hairstyling,
+    /// Any job not listed above, by its URL slug
+    Other(String),
+}
+
+const CREW_ROLE_SLUGS: [(CrewRole, &str); 28] = [
+    (CrewRole::Director, "director"),
+    (CrewRole::CoDirector, "co-director"),
+    (CrewRole::Producer, "producer"),
+    (CrewRole::ExecutiveProducer, "executive-producer"),
+    (CrewRole::Writer, "writer"),
+    (CrewRole::OriginalWriter, "original-writer"),
+    (CrewRole::Story, "story"),
+    (CrewRole::Casting, "casting"),
+    (CrewRole::Editor, "editor"),
+    (CrewRole::Cinematography, "cinematography"),
+    (CrewRole::AssistantDirector, "assistant-director"),
+    (CrewRole::AdditionalDirecting, "additional-directing"),
+    (CrewRole::CameraOperator, "camera-operator"),
+    (CrewRole::AdditionalPhotography, "additional-photography"),
+    (CrewRole::ProductionDesign, "production-design"),
+    (CrewRole::ArtDirection, "art-direction"),
+    (CrewRole::SetDecoration, "set-decoration"),
+    (CrewRole::SpecialEffects, "special-effects"),
+    (CrewRole::VisualEffects, "visual-effects"),
+    (CrewRole::TitleDesign, "title-design"),
+    (CrewRole::Stunts, "stunts"),
+    (CrewRole::Choreography, "choreography"),
+    (CrewRole::Composer, "composer"),
+    (CrewRole::Songs, "songs"),
+    (CrewRole::Sound, "sound"),
+    (CrewRole::CostumeDesign, "costume-design"),
+    (CrewRole::Makeup, "makeup"),
+    (CrewRole::Hairstyling, "hairstyling"),
+];
+
+impl CrewRole {
+    pub fn from_slug(slug: &str) -> Self {
+        CREW_ROLE_SLUGS.iter()
+            .find(|(_, s)| *s == slug)
+            .map(|(role, _)| role.clone())
+            .unwrap_or_else(|| CrewRole::Other(slug.to_string()))
+    }
+
+    pub fn slug(&self) -> &str {
+        match self {
+            CrewRole::Other(slug) => slug,
+            role => CREW_ROLE_SLUGS.iter()
+                .find(|(r, _)| r == role)
+                .map(|(_, slug)| *slug)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<String> for CrewRole {
+    fn from(slug: String) -> Self {
+        Self::from_slug(&slug)
+    }
+}
+
+impl From<CrewRole> for String {
+    fn from(role: CrewRole) -> Self {
+        role.slug().to_string()
+    }
+}
+
+impl std::fmt::Display for CrewRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.slug())
+    }
+}
+
+/// A film's crew, grouped by job.
+///
+/// Jobs are ordered as [`CrewRole`] declares them, directors first; the
+/// people within a job are in the order the page lists them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Crew(BTreeMap<CrewRole, Vec<MoviePerson>>);
+
+impl Crew {
+    /// People credited with `role`, empty if there are none
+    pub fn get(&self, role: &CrewRole) -> &[MoviePerson] {
+        self.0.get(role).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn directors(&self) -> &[MoviePerson] {
+        self.get(&CrewRole::Director)
+    }
+
+    /// Names of the people credited with `role`
+    pub fn names(&self, role: &CrewRole) -> Vec<String> {
+        self.get(role).iter().map(|person| person.name.clone()).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&CrewRole, &[MoviePerson])> {
+        self.0.iter().map(|(role, people)| (role, people.as_slice()))
+    }
+
+    /// Number of credits across every role
+    pub fn len(&self) -> usize {
+        self.0.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, role: CrewRole, person: MoviePerson) {
+        self.0.entry(role).or_default().push(person);
+    }
 }
 
 impl Movie {
     pub async fn new(slug: &str) -> Result<Self> {
-        let client = Client::new();
-        let url = format!("{}/film/{}", DOMAIN, slug);
+        Self::new_with_client(&Client::new(), slug).await
+    }
+
+    /// Fetch a film through `client`, using its in-memory film cache when enabled.
+    ///
+    /// Concurrent calls for the same slug share a single request.
+    pub async fn new_with_client(client: &Client, slug: &str) -> Result<Self> {
+        let Some(cache) = client.movie_cache() else {
+            return Self::fetch(client, slug).await;
+        };
+
+        let detached = client.without_movie_cache();
+        if client.bypasses_cache() {
+            let movie = Self::fetch(&detached, slug).await?;
+            cache.insert(slug.to_string(), movie.clone());
+            return Ok(movie.with_client(client));
+        }
+
+        let movie = cache
+            .get_or_try_insert_with(slug.to_string(), || Self::fetch(&detached, slug))
+            .await?;
+        Ok(movie.with_client(client))
+    }
+
+    async fn fetch(client: &Client, slug: &str) -> Result<Self> {
+        let url = build_film_url(client.base_url(), slug);
         
-        let dom = client.get_page(&url).await?;
+        let dom = client.get_page(&url).await.map_err(|e| {
+            if e.is_not_found() {
+                Error::MovieNotFound(slug.to_string())
+            } else {
+                e
+            }
+        })?;
         
         // Parse movie data from HTML
-        let movie = Self::parse_movie_data(&dom, slug, &url)?;
+        let movie = Self::parse_movie_data(&dom, client, slug, &url)?;
         
         Ok(movie)
     }
 
-    fn parse_movie_data(dom: &scraper::Html, slug: &str, url: &str) -> Result<Self> {
-        use scraper::Selector;
-        use serde_json::json;
-        
+    /// The client this movie was fetched with, reused for follow-up requests
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    fn with_client(mut self, client: &Client) -> Self {
+        self.client = client.clone();
+        self
+    }
+
+    /// The film as JSON in the shape used before its fields were typed:
+    /// `runtime` and `rating` as strings, themes and nanogenres folded into
+    /// `genres` as `{"type", "name"}` objects, and crew keyed by role slug.
+    pub fn to_legacy_json(&self) -> Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        let Some(object) = value.as_object_mut() else {
+            return value;
+        };
+
+        for key in ["runtime_minutes", "average_rating", "rating_count", "themes", "mini_themes", "nanogenres"] {
+            object.remove(key);
+        }
+        object.insert("runtime".to_string(), json!(self.runtime_minutes.map(|minutes| minutes.to_string())));
+        object.insert("rating".to_string(), json!(self.average_rating.map(|rating| format!("{:.1}", rating))));
+
+        let genres = self.genres.iter().map(|genre| ("genre", &genre.name))
+            .chain(self.themes.iter().map(|theme| ("theme", &theme.name)))
+            .chain(self.mini_themes.iter().map(|theme| ("mini-theme", &theme.name)))
+            .chain(self.nanogenres.iter().map(|nanogenre| ("nanogenre", &nanogenre.name)))
+            .map(|(kind, name)| json!({ "type": kind, "name": name }))
+            .collect();
+        object.insert("genres".to_string(), Value::Array(genres));
+
+        let mut crew = serde_json::Map::new();
+        crew.insert("director".to_string(), json!([]));
+        for (role, people) in self.crew.iter() {
+            let people = people.iter()
+                .map(|person| json!({ "name": person.name, "slug": person.slug, "url": person.url }))
+                .collect();
+            crew.insert(role.slug().to_string(), Value::Array(people));
+        }
+        object.insert("crew".to_string(), Value::Object(crew));
+
+        let reviews = self.popular_reviews.iter()
+            .map(|review| json!({
+                "author": review.author,
+                "rating": review.rating,
+                "content": review.body,
+                "likes": review.like_count,
+                "date": review.watched_date.map(|date| date.to_string()).unwrap_or_default(),
+            }))
+            .collect();
+        object.insert("popular_reviews".to_string(), Value::Array(reviews));
+
+        value
+    }
+
+    /// Read a film saved as JSON, in the current shape or the one written by [`Movie::to_legacy_json`]
+    pub fn from_legacy_json(value: Value) -> Result<Self> {
+        Self::from_legacy_json_with_client(&Client::new(), value)
+    }
+
+    /// Like [`Movie::from_legacy_json`], building links from the base URL of `client` and keeping it for follow-up requests
+    pub fn from_legacy_json_with_client(client: &Client, mut value: Value) -> Result<Self> {
+        if let Some(object) = value.as_object_mut() {
+            upgrade_legacy_json(object, client);
+        }
+        let movie: Self = serde_json::from_value(value)?;
+        Ok(movie.with_client(client))
+    }
+
+    fn parse_movie_data(dom: &Html, client: &Client, slug: &str, url: &str) -> Result<Self> {
         let title_selector = Selector::parse("h1.headline-1").unwrap();
-        let year_selector = Selector::parse(".film-poster").unwrap();
+        let poster_selector = Selector::parse(".film-poster").unwrap();
+        let original_title_selector = Selector::parse(".film-header-group .originalname").unwrap();
         let rating_selector = Selector::parse(".average-rating").unwrap();
-        let _runtime_selector = Selector::parse("p.text-link").unwrap();
+        let footer_selector = Selector::parse("p.text-link.text-footer").unwrap();
+        let tmdb_selector = Selector::parse("a[data-track-action=\"TMDB\"]").unwrap();
+        let imdb_selector = Selector::parse("a[data-track-action=\"IMDb\"]").unwrap();
+        let backdrop_selector = Selector::parse("#backdrop").unwrap();
+        let trailer_selector = Selector::parse(".trailer-link a, a.play[href*=\"youtube\"]").unwrap();
         let tagline_selector = Selector::parse(".tagline").unwrap();
         let description_selector = Selector::parse(".truncate p").unwrap();
-        let genres_selector = Selector::parse("#tab-genres .text-slug").unwrap();
+
+        let json_ld = parse_json_ld(dom);
+        let poster_element = dom.select(&poster_selector).next();
         
         let title = dom.select(&title_selector)
             .next()
             .map(|el| el.inner_html())
+            .or_else(|| json_ld["name"].as_str().map(str::to_string))
             .unwrap_or_else(|| slug.replace('-', " "));
 
-        let year = dom.select(&year_selector)
+        let original_title = dom.select(&original_title_selector)
             .next()
+            .map(|el| clean_text(&el.text().collect::<String>()))
+            .filter(|original| !original.is_empty() && *original != title);
+
+        let year = poster_element
             .and_then(|el| el.value().attr("data-film-year"))
-            .and_then(|year_str| year_str.parse().ok());
+            .and_then(|year_str| year_str.parse().ok())
+            .or_else(|| {
+                json_ld["releasedEvent"][0]["startDate"].as_str()
+                    .and_then(|date| date.get(..4))
+                    .and_then(|year_str| year_str.parse().ok())
+            });
+
+        let movie_id = poster_element
+            .and_then(|el| el.value().attr("data-film-id"))
+            .and_then(|id| id.parse().ok());
+
+        // The structured data has two decimals where the page shows one
+        let average_rating = json_ld["aggregateRating"]["ratingValue"].as_f64()
+            .map(|rating| rating as f32)
+            .or_else(|| {
+                dom.select(&rating_selector)
+                    .next()
+                    .and_then(|el| clean_text(&el.text().collect::<String>()).parse().ok())
+            });
 
-        let rating = dom.select(&rating_selector)
+        let rating_count = json_ld["aggregateRating"]["ratingCount"].as_u64()
+            .and_then(|count| u32::try_from(count).ok());
+
+        // The footer reads e.g. "136 mins   More at IMDb TMDB"
+        let runtime_minutes = dom.select(&footer_selector)
             .next()
-            .map(|el| el.inner_html());
+            .and_then(|el| parse_runtime(&el.text().collect::<String>()));
+
+        let tmdb_link = dom.select(&tmdb_selector)
+            .next()
+            .and_then(|el| el.value().attr("href"))
+            .map(str::to_string);
+
+        let imdb_link = dom.select(&imdb_selector)
+            .next()
+            .and_then(|el| el.value().attr("href"))
+            .map(str::to_string);
+
+        let poster = json_ld["image"].as_str()
+            .map(|image| image.split('?').next().unwrap_or(image).to_string());
+
+        let banner = dom.select(&backdrop_selector)
+            .next()
+            .and_then(|el| el.value().attr("data-backdrop"))
+            .map(str::to_string);
+
+        let trailer = dom.select(&trailer_selector)
+            .filter_map(|el| el.value().attr("href"))
+            .find_map(parse_trailer);
 
         let tagline = dom.select(&tagline_selector)
             .next()
@@ -111,69 +550,513 @@ impl Movie {
             .next()
             .map(|el| el.inner_html());
 
-        // Convert genres to match letterboxdpy format
-        let genres: Vec<HashMap<String, serde_json::Value>> = dom.select(&genres_selector)
-            .map(|el| {
-                let mut genre_map = HashMap::new();
-                genre_map.insert("type".to_string(), json!("genre"));
-                genre_map.insert("name".to_string(), json!(el.inner_html()));
-                genre_map
-            })
-            .collect();
-
-        // Initialize crew as HashMap with director key
-        let mut crew = HashMap::new();
-        crew.insert("director".to_string(), Vec::new());
+        let (genres, themes, mini_themes, nanogenres) = parse_genres_tab(dom, client);
+        let crew = parse_crew(dom, client);
+        let details = parse_details(dom, &crew);
 
         Ok(Movie {
             url: url.to_string(),
             slug: slug.to_string(),
-            movie_id: None, // TODO: Extract movie ID
+            movie_id,
             title,
-            original_title: None, // TODO: Extract original title
-            runtime: None, // TODO: Parse runtime
-            rating,
+            original_title,
+            runtime_minutes,
+            average_rating,
+            rating_count,
             year,
-            tmdb_link: None, // TODO: Extract TMDB link
-            imdb_link: None, // TODO: Extract IMDB link
-            poster: None, // TODO: Extract poster URL
-            banner: None, // TODO: Extract banner URL
+            tmdb_link,
+            imdb_link,
+            poster,
+            banner,
             tagline,
             description,
-            trailer: None, // TODO: Parse trailer
-            alternative_titles: Vec::new(), // TODO: Extract alternative titles
-            details: None, // TODO: Parse movie details
+            trailer,
+            alternative_titles: parse_alternative_titles(dom),
+            details,
             genres,
-            cast: Vec::new(), // TODO: Parse cast
+            themes,
+            mini_themes,
+            nanogenres,
+            cast: parse_cast(dom, client),
             crew,
-            popular_reviews: Vec::new(), // TODO: Parse reviews
+            popular_reviews: parse_popular_reviews(dom, client),
+            client: client.clone(),
         })
     }
 
-    pub async fn get_watchers(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/film/{}/members/", DOMAIN, self.slug);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse watchers from the page
-        Ok(HashMap::new())
+    /// The first `limit` members matching `filter`; popular films have millions, so use [`Movie::watchers`] to page through more
+    pub async fn get_watchers(&self, filter: MemberFilter, limit: usize) -> Result<Vec<FilmMember>> {
+        self.watchers(filter).take(limit).try_collect().await
     }
 
-    pub async fn get_reviews(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/film/{}/reviews/", DOMAIN, self.slug);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse reviews from the page
-        Ok(HashMap::new())
+    /// Stream of the members who watched, liked, rated, reviewed or watchlisted the film
+    pub fn watchers(&self, filter: MemberFilter) -> Paginator<FilmMember> {
+        let url = build_film_section_url(self.client.base_url(), &self.slug, filter.section());
+        let client = self.client.clone();
+        Paginator::for_url(&self.client, &url, move |dom, _| Ok(parse_film_members(dom, &client)))
     }
 
-    pub async fn get_similar(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/film/{}/similar/", DOMAIN, self.slug);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse similar movies from the page
-        Ok(HashMap::new())
+    /// Watch, list and like totals from the film's stats bar
+    pub async fn get_member_counts(&self) -> Result<MemberCounts> {
+        let dom = self.client.get_page(&self.stats_fragment_url("stats")).await?;
+        Ok(parse_member_counts(&dom))
+    }
+
+    /// Rating histogram, fans, member totals and Top 250 rank
+    pub async fn get_stats(&self) -> Result<FilmStats> {
+        let histogram = self.client.get_page(&self.stats_fragment_url("rating-histogram")).await?;
+        let stats = self.client.get_page(&self.stats_fragment_url("stats")).await?;
+
+        Ok(parse_film_stats(&histogram, &stats))
+    }
+
+    /// The film page loads its ratings and stats bar from these fragments
+    fn stats_fragment_url(&self, fragment: &str) -> String {
+        self.client.url(&format!("csi/film/{}/{}/", self.slug, fragment))
+    }
+
+    /// The first `limit` reviews matching `query`; use [`Movie::reviews`] to page through more
+    pub async fn get_reviews(&self, query: ReviewQuery, limit: usize) -> Result<Vec<Review>> {
+        self.reviews(query)?.take(limit).try_collect().await
+    }
+
+    /// Stream of the film's reviews, fails if the query's rating is not a valid star rating
+    pub fn reviews(&self, query: ReviewQuery) -> Result<Paginator<Review>> {
+        let url = self.client.url(&query.path(&self.slug)?);
+        Ok(review_listing(&self.client, &url))
+    }
+
+    /// Every release listed on the film's releases tab, grouped by type as shown
+    pub async fn get_releases(&self) -> Result<Vec<Release>> {
+        let url = build_film_section_url(self.client.base_url(), &self.slug, "releases");
+        let dom = self.client.get_page(&url).await?;
+
+        Ok(parse_releases(&dom))
+    }
+
+    /// Films Letterboxd lists as similar, most similar first
+    pub async fn get_similar(&self) -> Result<Vec<SimilarFilm>> {
+        let url = build_film_section_url(self.client.base_url(), &self.slug, "similar");
+        let dom = self.client.get_page(&url).await?;
+
+        Ok(parse_similar_films(&dom, &self.client))
+    }
+}
+
+/// The film's JSON-LD block, or `Value::Null` when the page has none
+fn parse_json_ld(dom: &Html) -> serde_json::Value {
+    let selector = Selector::parse("script[type=\"application/ld+json\"]").unwrap();
+    dom.select(&selector)
+        .find_map(|script| {
+            // Letterboxd wraps the JSON in a commented-out CDATA section
+            let text = script.text().collect::<String>();
+            let json = text
+                .trim()
+                .trim_start_matches("/* <![CDATA[ */")
+                .trim_end_matches("/* ]]> */")
+                .trim();
+            serde_json::from_str(json).ok()
+        })
+        .unwrap_or(serde_json::Value::Null)
+}
+
+/// A YouTube trailer from its embed link, e.g. `//www.youtube.com/embed/m8e-FF8MsqU?rel=0`
+fn parse_trailer(href: &str) -> Option<MovieTrailer> {
+    let id_regex = Regex::new(r"youtube\.com/(?:embed/|watch\?v=)([\w-]+)").unwrap();
+    let id = id_regex.captures(href)?[1].to_string();
+
+    Some(MovieTrailer {
+        link: format!("https://www.youtube.com/watch?v={}", id),
+        embed_url: format!("https://www.youtube.com/embed/{}", id),
+        id,
+    })
+}
+
+/// Links listed under each heading of a tab, e.g. "Studios" in `#tab-details`
+fn tab_sections<'a>(dom: &'a Html, tab: &str) -> Vec<(String, Vec<ElementRef<'a>>)> {
+    let tab_selector = Selector::parse(tab).unwrap();
+    let link_selector = Selector::parse("a").unwrap();
+
+    let Some(tab) = dom.select(&tab_selector).next() else {
+        return Vec::new();
+    };
+
+    // Each <h3> heading is followed by the block listing its entries
+    let mut sections = Vec::new();
+    let mut heading = None;
+    for child in tab.children().filter_map(ElementRef::wrap) {
+        if child.value().name() == "h3" {
+            heading = Some(clean_text(&child.text().collect::<String>()));
+        } else if let Some(heading) = heading.take() {
+            sections.push((heading, child.select(&link_selector).collect()));
+        }
+    }
+    sections
+}
+
+/// Genres, themes, mini-themes and nanogenres linked from `#tab-genres`, told apart by their browse URLs
+fn parse_genres_tab(dom: &Html, client: &Client) -> (Vec<Genre>, Vec<Theme>, Vec<Theme>, Vec<Nanogenre>) {
+    let selector = Selector::parse("#tab-genres a.text-slug[href]").unwrap();
+
+    let mut genres = Vec::new();
+    let mut themes = Vec::new();
+    let mut mini_themes = Vec::new();
+    let mut nanogenres = Vec::new();
+    for link in dom.select(&selector) {
+        let href = link.value().attr("href").unwrap_or_default();
+        // Links look like /films/genre/action/; "Show All…" links to the film's own themes page
+        let mut parts = href.trim_matches('/').split('/');
+        let (Some("films"), Some(kind), Some(slug)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let name = clean_text(&link.text().collect::<String>());
+        let slug = slug.to_string();
+        let url = client.url(href);
+
+        match kind {
+            "genre" => genres.push(Genre { name, slug, url }),
+            "theme" => themes.push(Theme { name, slug, url }),
+            "mini-theme" => mini_themes.push(Theme { name, slug, url }),
+            "nanogenre" => nanogenres.push(Nanogenre { name, slug, url }),
+            _ => {}
+        }
+    }
+
+    (genres, themes, mini_themes, nanogenres)
+}
+
+fn parse_similar_films(dom: &Html, client: &Client) -> Vec<SimilarFilm> {
+    let poster_selector = Selector::parse(".poster-list .film-poster[data-film-slug]").unwrap();
+    let img_selector = Selector::parse("img").unwrap();
+
+    dom.select(&poster_selector)
+        .filter_map(|poster| {
+            let slug = poster.value().attr("data-film-slug")?;
+            let name = poster.value().attr("data-film-name")
+                .or_else(|| poster.select(&img_selector).next().and_then(|img| img.value().attr("alt")))
+                .unwrap_or(slug);
+
+            Some(SimilarFilm {
+                name: name.to_string(),
+                slug: slug.to_string(),
+                url: build_film_url(client.base_url(), slug),
+                film_id: poster.value().attr("data-film-id").and_then(|id| id.parse().ok()),
+                year: poster.value().attr("data-film-release-year")
+                    .or_else(|| poster.value().attr("data-film-year"))
+                    .and_then(|year| year.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+fn parse_crew(dom: &Html, client: &Client) -> Crew {
+    let mut crew = Crew::default();
+
+    for (_, links) in tab_sections(dom, "#tab-crew") {
+        for link in links {
+            let Some(href) = link.value().attr("href") else {
+                continue;
+            };
+            // Crew links look like /director/lilly-wachowski/
+            let mut parts = href.trim_matches('/').split('/');
+            let (Some(role), Some(slug)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+
+            crew.push(CrewRole::from_slug(role), MoviePerson {
+                name: clean_text(&link.text().collect::<String>()),
+                role_name: None,
+                slug: slug.to_string(),
+                url: client.url(href),
+            });
+        }
+    }
+
+    crew
+}
+
+fn parse_cast(dom: &Html, client: &Client) -> Vec<MoviePerson> {
+    let selector = Selector::parse("#tab-cast .cast-list a.text-slug").unwrap();
+
+    dom.select(&selector)
+        .filter_map(|link| {
+            let href = link.value().attr("href")?;
+            // Cast links look like /actor/keanu-reeves/, with the character in the title
+            let slug = href.trim_matches('/').rsplit('/').next()?.to_string();
+
+            Some(MoviePerson {
+                name: clean_text(&link.text().collect::<String>()),
+                role_name: link.value().attr("title").map(str::to_string).filter(|role| !role.is_empty()),
+                slug,
+                url: client.url(href),
+            })
+        })
+        .collect()
+}
+
+/// Studios, countries and languages, with the main crew; `None` when the page lists none of them
+fn parse_details(dom: &Html, crew: &Crew) -> Option<MovieDetails> {
+    let mut production_companies = Vec::new();
+    let mut countries = Vec::new();
+    let mut languages = Vec::new();
+
+    for (heading, links) in tab_sections(dom, "#tab-details") {
+        let names = links.iter().map(|link| clean_text(&link.text().collect::<String>()));
+        match heading.to_lowercase().as_str() {
+            "studio" | "studios" => production_companies.extend(names),
+            "country" | "countries" => countries.extend(names),
+            // The primary language is usually listed again among the spoken ones
+            "language" | "languages" | "primary language" | "spoken languages" => {
+                for name in names {
+                    if !languages.contains(&name) {
+                        languages.push(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let details = MovieDetails {
+        director: crew.names(&CrewRole::Director),
+        writer: crew.names(&CrewRole::Writer),
+        producer: crew.names(&CrewRole::Producer),
+        cinematographer: crew.names(&CrewRole::Cinematography),
+        editor: crew.names(&CrewRole::Editor),
+        composer: crew.names(&CrewRole::Composer),
+        production_companies,
+        countries,
+        languages,
+    };
+
+    let is_empty = details.director.is_empty()
+        && details.writer.is_empty()
+        && details.producer.is_empty()
+        && details.cinematographer.is_empty()
+        && details.editor.is_empty()
+        && details.composer.is_empty()
+        && details.production_companies.is_empty()
+        && details.countries.is_empty()
+        && details.languages.is_empty();
+    (!is_empty).then_some(details)
+}
+
+/// Alternative titles, listed comma-separated under their own heading in `#tab-details`
+fn parse_alternative_titles(dom: &Html) -> Vec<String> {
+    let tab_selector = Selector::parse("#tab-details").unwrap();
+    let Some(tab) = dom.select(&tab_selector).next() else {
+        return Vec::new();
+    };
+
+    let mut after_heading = false;
+    for child in tab.children().filter_map(ElementRef::wrap) {
+        if child.value().name() == "h3" {
+            after_heading = clean_text(&child.text().collect::<String>()).eq_ignore_ascii_case("alternative titles");
+        } else if after_heading {
+            return clean_text(&child.text().collect::<String>())
+                .split(", ")
+                .map(str::to_string)
+                .filter(|title| !title.is_empty())
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+fn parse_popular_reviews(dom: &Html, client: &Client) -> Vec<Review> {
+    let review_selector = Selector::parse(".film-popular-review .film-detail").unwrap();
+    dom.select(&review_selector)
+        .filter_map(|review| parse_review(review, client))
+        .collect()
+}
+
+/// Date of a legacy review, which was stored as the page showed it.
+///
+/// Dates that cannot be read are dropped rather than failing the whole film.
+fn parse_legacy_date(text: &str) -> Option<NaiveDate> {
+    let text = text.trim();
+    text.get(..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .or_else(|| {
+            let (year, month, day) = parse_written_date(text).ok()?;
+            NaiveDate::from_ymd_opt(year, month, day)
+        })
+}
+
+/// Rewrite the untyped fields of a legacy film in place; current fields are left alone
+fn upgrade_legacy_json(object: &mut serde_json::Map<String, Value>, client: &Client) {
+    // Numbers were stored as the page's text, e.g. "136" and "4.2"
+    let text = |value: Value| match value {
+        Value::String(text) => text.trim().to_string(),
+        other => other.to_string(),
+    };
+    if let Some(runtime) = object.remove("runtime") {
+        object.insert("runtime_minutes".to_string(), json!(text(runtime).parse::<u32>().ok()));
+    }
+    if let Some(rating) = object.remove("rating") {
+        object.insert("average_rating".to_string(), json!(text(rating).parse::<f32>().ok()));
+    }
+
+    // Legacy reviews had their text as `content` and only a few other fields
+    if let Some(Value::Array(reviews)) = object.get_mut("popular_reviews") {
+        for review in reviews.iter_mut().filter(|review| review.get("content").is_some()) {
+            let date = review["date"].as_str().and_then(parse_legacy_date);
+            let body = review["content"].clone();
+            *review = json!({
+                "author": review["author"],
+                "author_display_name": review["author"],
+                "rating": review["rating"],
+                "liked": false,
+                "rewatch": false,
+                "watched_date": date,
+                "body_html": "",
+                "body": body,
+                "contains_spoilers": false,
+                "like_count": review["likes"],
+                "comment_count": 0,
+            });
+        }
+    }
+
+    // Legacy genres are `{"type", "name"}` objects mixing genres with themes and nanogenres
+    let Some(Value::Array(entries)) = object.get("genres").cloned() else {
+        return;
+    };
+    if !entries.iter().any(|entry| entry.get("type").is_some()) {
+        return;
+    }
+
+    let mut genres = Vec::new();
+    let mut themes = Vec::new();
+    let mut mini_themes = Vec::new();
+    let mut nanogenres = Vec::new();
+    for entry in entries {
+        let kind = entry["type"].as_str().unwrap_or("genre");
+        let Some(name) = entry["name"].as_str() else {
+            continue;
+        };
+        let slug = name.to_lowercase().split_whitespace().collect::<Vec<_>>().join("-");
+        let item = json!({
+            "name": name,
+            "url": client.url(&format!("films/{}/{}/", kind, slug)),
+            "slug": slug,
+        });
+
+        match kind {
+            "genre" => genres.push(item),
+            "theme" => themes.push(item),
+            "mini-theme" => mini_themes.push(item),
+            "nanogenre" => nanogenres.push(item),
+            _ => {}
+        }
+    }
+
+    object.insert("genres".to_string(), Value::Array(genres));
+    object.insert("themes".to_string(), Value::Array(themes));
+    object.insert("mini_themes".to_string(), Value::Array(mini_themes));
+    object.insert("nanogenres".to_string(), Value::Array(nanogenres));
+}
+
+fn parse_film_members(dom: &Html, client: &Client) -> Vec<FilmMember> {
+    let row_selector = Selector::parse("table.person-table tbody tr").unwrap();
+    let avatar_selector = Selector::parse(".person-summary a.avatar").unwrap();
+    let img_selector = Selector::parse("img").unwrap();
+    let name_selector = Selector::parse(".person-summary a.name").unwrap();
+    let rating_selector = Selector::parse(".rating").unwrap();
+    let liked_selector = Selector::parse(".icon-liked").unwrap();
+    let review_selector = Selector::parse("a.icon-review[href]").unwrap();
+
+    dom.select(&row_selector)
+        .filter_map(|row| {
+            let avatar = row.select(&avatar_selector).next()?;
+            let username = avatar.value().attr("href")?.trim_matches('/').to_string();
+            if username.is_empty() {
+                return None;
+            }
+
+            Some(FilmMember {
+                display_name: row.select(&name_selector)
+                    .next()
+                    .map(|a| clean_text(&a.text().collect::<String>()))
+                    .unwrap_or_else(|| username.clone()),
+                url: client.url(&format!("{}/", username)),
+                avatar: avatar.select(&img_selector)
+                    .next()
+                    .and_then(|img| img.value().attr("src"))
+                    .map(str::to_string),
+                rating: row.select(&rating_selector)
+                    .next()
+                    .and_then(|el| el.value().attr("class"))
+                    .and_then(parse_rating_class),
+                liked: row.select(&liked_selector).next().is_some(),
+                review_url: row.select(&review_selector)
+                    .next()
+                    .and_then(|a| a.value().attr("href"))
+                    .map(|href| client.url(href)),
+                username,
+            })
+        })
+        .collect()
+}
+
+fn parse_member_counts(dom: &Html) -> MemberCounts {
+    // Tooltips hold the exact count, e.g. "Watched by 3,912,345 members", the link text a rounded one
+    let count = |class: &str| -> Option<u32> {
+        let selector = Selector::parse(&format!(".film-stats .{} a", class)).unwrap();
+        let link = dom.select(&selector).next()?;
+        link.value().attr("title")
+            .or_else(|| link.value().attr("data-original-title"))
+            .and_then(extract_numeric_text)
+            .or_else(|| parse_shorthand(&link.text().collect::<String>()))
+    };
+
+    MemberCounts {
+        watched: count("filmstat-watches"),
+        listed: count("filmstat-lists"),
+        liked: count("filmstat-likes"),
+    }
+}
+
+fn parse_film_stats(histogram_dom: &Html, stats_dom: &Html) -> FilmStats {
+    let bar_selector = Selector::parse(".rating-histogram-bar").unwrap();
+    let link_selector = Selector::parse("a").unwrap();
+    let average_selector = Selector::parse(".average-rating a").unwrap();
+    let fans_selector = Selector::parse("a.all-link[href$=\"/fans/\"]").unwrap();
+    let top_250_selector = Selector::parse(".film-stats .filmstat-top250 a").unwrap();
+
+    // Each bar reads e.g. "12,345 half-★ ratings (1%)"; empty bars have no link
+    let mut histogram = [0; 10];
+    for (bucket, bar) in histogram.iter_mut().zip(histogram_dom.select(&bar_selector)) {
+        *bucket = bar.select(&link_selector)
+            .next()
+            .map(|link| clean_text(&link.text().collect::<String>()))
+            .and_then(|text| text.split_whitespace().next().and_then(extract_numeric_text))
+            .unwrap_or(0);
+    }
+
+    // The tooltip reads "Weighted average of 4.23 based on 1,834,211 ratings"
+    let average = histogram_dom.select(&average_selector).next();
+    let tooltip = average
+        .and_then(|a| a.value().attr("data-original-title").or_else(|| a.value().attr("title")))
+        .map(clean_text)
+        .unwrap_or_default();
+    let tooltip_regex = Regex::new(r"of ([\d.]+) based on ([\d,]+)").unwrap();
+    let captures = tooltip_regex.captures(&tooltip);
+
+    FilmStats {
+        average_rating: captures.as_ref()
+            .and_then(|c| c[1].parse().ok())
+            .or_else(|| average.and_then(|a| clean_text(&a.text().collect::<String>()).parse().ok())),
+        rating_count: captures.as_ref()
+            .and_then(|c| extract_numeric_text(&c[2]))
+            .unwrap_or_else(|| histogram.iter().sum()),
+        histogram,
+        fans: histogram_dom.select(&fans_selector)
+            .next()
+            .and_then(|a| a.text().collect::<String>().split_whitespace().next().and_then(parse_shorthand)),
+        members: parse_member_counts(stats_dom),
+        top_250_rank: stats_dom.select(&top_250_selector)
+            .next()
+            .and_then(|a| extract_numeric_text(&a.text().collect::<String>())),
     }
 }
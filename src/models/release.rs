@@ -0,0 +1,95 @@
+use chrono::NaiveDate;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use crate::utils::parser::clean_text;
+
+/// One country's release of a film, from [`Movie::get_releases`](crate::Movie::get_releases)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Release {
+    pub country: String,
+    pub date: NaiveDate,
+    pub release_type: ReleaseType,
+    /// Age rating in that country, e.g. "R" or "15"
+    pub certification: Option<String>,
+    /// Details such as the festival of a premiere
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReleaseType {
+    Premiere,
+    TheatricalLimited,
+    Theatrical,
+    Digital,
+    Physical,
+    Tv,
+}
+
+impl ReleaseType {
+    /// The type a section of the releases tab lists, from its heading
+    fn from_heading(heading: &str) -> Option<Self> {
+        match heading.to_lowercase().as_str() {
+            "premiere" | "premieres" => Some(ReleaseType::Premiere),
+            "limited" | "theatrical limited" => Some(ReleaseType::TheatricalLimited),
+            "theatrical" => Some(ReleaseType::Theatrical),
+            "digital" => Some(ReleaseType::Digital),
+            "physical" => Some(ReleaseType::Physical),
+            "tv" => Some(ReleaseType::Tv),
+            _ => None,
+        }
+    }
+}
+
+/// Releases listed in `#tab-releases`, in the order shown
+pub(crate) fn parse_releases(dom: &Html) -> Vec<Release> {
+    let section_selector = Selector::parse("#tab-releases .release-table").unwrap();
+    let heading_selector = Selector::parse("h3").unwrap();
+    let item_selector = Selector::parse(".listitem").unwrap();
+    let date_selector = Selector::parse(".date").unwrap();
+    let country_selector = Selector::parse(".release-country").unwrap();
+
+    let mut releases = Vec::new();
+    for section in dom.select(&section_selector) {
+        let Some(release_type) = section.select(&heading_selector)
+            .next()
+            .and_then(|h3| ReleaseType::from_heading(&clean_text(&h3.text().collect::<String>())))
+        else {
+            continue;
+        };
+
+        for item in section.select(&item_selector) {
+            // Dates read e.g. "31 Mar 1999"
+            let Some(date) = item.select(&date_selector)
+                .next()
+                .and_then(|el| NaiveDate::parse_from_str(&clean_text(&el.text().collect::<String>()), "%d %b %Y").ok())
+            else {
+                continue;
+            };
+
+            releases.extend(item.select(&country_selector)
+                .filter_map(|country| parse_release_country(country, date, release_type)));
+        }
+    }
+    releases
+}
+
+fn parse_release_country(country: ElementRef, date: NaiveDate, release_type: ReleaseType) -> Option<Release> {
+    let name_selector = Selector::parse(".name").unwrap();
+    let certification_selector = Selector::parse(".release-certification-badge .label").unwrap();
+    let note_selector = Selector::parse(".release-note").unwrap();
+
+    let text = |selector: &Selector| -> Option<String> {
+        country.select(selector)
+            .next()
+            .map(|el| clean_text(&el.text().collect::<String>()))
+            .filter(|text| !text.is_empty())
+    };
+
+    Some(Release {
+        country: text(&name_selector)?,
+        date,
+        release_type,
+        certification: text(&certification_selector),
+        note: text(&note_selector),
+    })
+}
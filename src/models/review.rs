@@ -0,0 +1,217 @@
+use chrono::NaiveDate;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use crate::core::{Client, Error, Paginator, Result};
+use crate::utils::parser::{clean_text, extract_and_convert_shorthand, extract_numeric_text, parse_rating_class};
+use crate::utils::validators::is_valid_rating;
+
+/// A member's review of a film
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Review {
+    /// Id of the diary entry the review belongs to
+    pub id: Option<u64>,
+    pub url: Option<String>,
+    /// Username of the author
+    pub author: String,
+    pub author_display_name: String,
+    pub film_slug: Option<String>,
+    /// Title of the film, in listings that show it
+    pub film_title: Option<String>,
+    pub rating: Option<f32>,
+    pub liked: bool,
+    pub rewatch: bool,
+    pub watched_date: Option<NaiveDate>,
+    /// Review text as the page renders it, without the spoiler warning
+    pub body_html: String,
+    /// Review text as plain text
+    pub body: String,
+    pub contains_spoilers: bool,
+    pub like_count: u32,
+    pub comment_count: u32,
+}
+
+/// Order of the reviews listed by [`Movie::reviews`](crate::Movie::reviews)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewSort {
+    /// Most liked recently, as the website lists them by default
+    #[default]
+    Popular,
+    Recent,
+    Earliest,
+    Highest,
+    Lowest,
+}
+
+impl ReviewSort {
+    /// Path segment selecting the order; the default order has none
+    fn path(self) -> Option<&'static str> {
+        match self {
+            ReviewSort::Popular => None,
+            ReviewSort::Recent => Some("added"),
+            ReviewSort::Earliest => Some("added-earliest"),
+            ReviewSort::Highest => Some("entry-rating"),
+            ReviewSort::Lowest => Some("entry-rating-lowest"),
+        }
+    }
+}
+
+/// Which of a film's reviews to list, and in what order.
+///
+/// ```
+/// use rustboxd::models::{ReviewQuery, ReviewSort};
+///
+/// let query = ReviewQuery::new()
+///     .sort(ReviewSort::Recent)
+///     .rating(4.5);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReviewQuery {
+    pub sort: ReviewSort,
+    /// Only reviews with this rating, 0.5 to 5.0 in half-star steps
+    pub rating: Option<f32>,
+    /// Only reviews by members the signed-in user follows
+    pub friends_only: bool,
+}
+
+impl ReviewQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn sort(mut self, sort: ReviewSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn rating(mut self, rating: f32) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    /// Needs a signed-in client; see [`Session`](crate::Session)
+    pub fn friends_only(mut self, friends_only: bool) -> Self {
+        self.friends_only = friends_only;
+        self
+    }
+
+    /// Path of the listing for `slug`, e.g. `film/the-matrix/reviews/rated/4.5/by/added/`
+    pub(crate) fn path(&self, slug: &str) -> Result<String> {
+        let mut path = format!("film/{}/", slug);
+        if self.friends_only {
+            path.push_str("friends/");
+        }
+        path.push_str("reviews/");
+        if let Some(rating) = self.rating {
+            if !is_valid_rating(rating) {
+                return Err(Error::InvalidInput(format!("Invalid rating {}: must be 0.5 to 5.0 in steps of 0.5", rating)));
+            }
+            path.push_str(&format!("rated/{}/", rating));
+        }
+        if let Some(sort) = self.sort.path() {
+            path.push_str(&format!("by/{}/", sort));
+        }
+        Ok(path)
+    }
+}
+
+/// A paged listing of reviews, such as a film's reviews or a member's reviews or liked reviews
+pub(crate) fn review_listing(client: &Client, url: &str) -> Paginator<Review> {
+    let parse_client = client.clone();
+    Paginator::for_url(client, url, move |dom, _| Ok(parse_reviews(dom, &parse_client)))
+}
+
+fn parse_reviews(dom: &Html, client: &Client) -> Vec<Review> {
+    let review_selector = Selector::parse(".film-detail").unwrap();
+    dom.select(&review_selector)
+        .filter_map(|review| parse_review(review, client))
+        .collect()
+}
+
+/// A review in the `.film-detail` layout of film pages, profiles and review listings.
+///
+/// Film pages link the review from its attribution line; member listings
+/// link it from the film's title instead and show the film's poster.
+pub(crate) fn parse_review(review: ElementRef, client: &Client) -> Option<Review> {
+    let context_selector = Selector::parse(".attribution a.context, .headline-2 a").unwrap();
+    let poster_selector = Selector::parse(".film-poster[data-film-slug]").unwrap();
+    let headline_selector = Selector::parse(".headline-2 a").unwrap();
+    let name_selector = Selector::parse(".attribution .name").unwrap();
+    let rating_selector = Selector::parse(".attribution .rating").unwrap();
+    let liked_selector = Selector::parse(".attribution .icon-liked").unwrap();
+    let rewatch_selector = Selector::parse(".attribution .icon-rewatch").unwrap();
+    let date_selector = Selector::parse(".attribution time[datetime]").unwrap();
+    let body_selector = Selector::parse(".body-text").unwrap();
+    let spoilers_selector = Selector::parse(".contains-spoilers").unwrap();
+    let likes_selector = Selector::parse(".like-link-target[data-count]").unwrap();
+    let comments_selector = Selector::parse("a.icon-comment").unwrap();
+
+    // Review links look like /someone/film/the-matrix/, or /someone/film/the-matrix/1/ for a rewatch
+    let href = review.select(&context_selector).next()?.value().attr("href")?;
+    let path: Vec<&str> = href.trim_matches('/').split('/').collect();
+    let author = review.value().attr("data-person")
+        .unwrap_or(path[0])
+        .to_string();
+    if author.is_empty() {
+        return None;
+    }
+    let poster = review.select(&poster_selector).next();
+    let film_slug = poster
+        .and_then(|poster| poster.value().attr("data-film-slug"))
+        .or_else(|| path.iter().skip_while(|part| **part != "film").nth(1).copied())
+        .map(str::to_string);
+    let film_title = review.select(&headline_selector)
+        .next()
+        .map(|a| clean_text(&a.text().collect::<String>()))
+        .or_else(|| poster.and_then(|poster| poster.value().attr("data-film-name")).map(str::to_string))
+        .filter(|title| !title.is_empty());
+
+    let body = review.select(&body_selector).next();
+    let contains_spoilers = body.is_some_and(|body| body.select(&spoilers_selector).next().is_some());
+    // Everything in the body but the spoiler warning
+    let paragraphs: Vec<ElementRef> = body
+        .map(|body| body.children()
+            .filter_map(ElementRef::wrap)
+            .filter(|child| !child.value().classes().any(|class| class == "contains-spoilers"))
+            .collect())
+        .unwrap_or_default();
+
+    Some(Review {
+        id: review.value().attr("data-object-id")
+            .and_then(|id| id.rsplit(':').next())
+            .and_then(|id| id.parse().ok()),
+        url: Some(client.url(href)),
+        author_display_name: review.select(&name_selector)
+            .next()
+            .map(|el| clean_text(&el.text().collect::<String>()))
+            .unwrap_or_else(|| author.clone()),
+        author,
+        film_slug,
+        film_title,
+        rating: review.select(&rating_selector)
+            .next()
+            .and_then(|el| el.value().attr("class"))
+            .and_then(parse_rating_class),
+        liked: review.select(&liked_selector).next().is_some(),
+        rewatch: review.select(&rewatch_selector).next().is_some(),
+        watched_date: review.select(&date_selector)
+            .next()
+            .and_then(|el| el.value().attr("datetime"))
+            .and_then(|date| NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok()),
+        body_html: paragraphs.iter().map(|p| p.html()).collect::<Vec<_>>().join("\n"),
+        body: paragraphs.iter()
+            .map(|p| clean_text(&p.text().collect::<String>()))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        contains_spoilers,
+        like_count: review.select(&likes_selector)
+            .next()
+            .and_then(|el| el.value().attr("data-count"))
+            .map(extract_and_convert_shorthand)
+            .unwrap_or(0),
+        comment_count: review.select(&comments_selector)
+            .next()
+            .and_then(|el| extract_numeric_text(&el.text().collect::<String>()))
+            .unwrap_or(0),
+    })
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
-use crate::core::{Client, Error, Result, constants::{DOMAIN, SEARCH_FILTERS}};
+use futures::{StreamExt, TryStreamExt};
+use crate::core::{Client, Error, Paginator, Result, constants::SEARCH_FILTERS};
+use crate::core::paginator::has_next_page;
+use crate::utils::transform::{build_letterboxd_url, build_search_url};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Search {
@@ -7,6 +10,11 @@ pub struct Search {
     pub search_filter: Option<String>,
     pub url: String,
     pub results: SearchResults,
+    /// Whether the first page links to more results
+    #[serde(skip)]
+    has_next: bool,
+    #[serde(skip)]
+    client: Client,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,33 +105,33 @@ pub struct SearchArticle {
 
 impl Search {
     pub async fn new(query: &str, search_filter: Option<&str>) -> Result<Self> {
+        Self::new_with_client(&Client::new(), query, search_filter).await
+    }
+
+    pub async fn new_with_client(client: &Client, query: &str, search_filter: Option<&str>) -> Result<Self> {
         if let Some(filter) = search_filter {
             if !SEARCH_FILTERS.contains(&filter) {
-                return Err(Error::Parse(format!("Invalid search filter: {}", filter)));
+                return Err(Error::InvalidInput(format!("Invalid search filter: {}", filter)));
             }
         }
 
-        let client = Client::new();
-        let encoded_query = urlencoding::encode(query);
-        
-        let url = if let Some(filter) = search_filter {
-            format!("{}/s/search/{}/{}", DOMAIN, filter, encoded_query)
-        } else {
-            format!("{}/s/search/{}", DOMAIN, encoded_query)
-        };
+        let url = build_search_url(client.base_url(), query, search_filter);
 
         let dom = client.get_page(&url).await?;
-        let results = Self::parse_search_results(&dom, search_filter)?;
+        let results = Self::parse_search_results(&dom, client.base_url(), &url, search_filter)?;
+        let has_next = has_next_page(&dom);
 
         Ok(Search {
             query: query.to_string(),
             search_filter: search_filter.map(|s| s.to_string()),
             url,
             results,
+            has_next,
+            client: client.clone(),
         })
     }
 
-    fn parse_search_results(dom: &scraper::Html, search_filter: Option<&str>) -> Result<SearchResults> {
+    fn parse_search_results(dom: &scraper::Html, base_url: &str, url: &str, search_filter: Option<&str>) -> Result<SearchResults> {
         use scraper::Selector;
         
         let mut results = SearchResults {
@@ -141,7 +149,7 @@ impl Search {
             Some("films") | None => {
                 let film_selector = Selector::parse(".film-detail").unwrap();
                 for element in dom.select(&film_selector) {
-                    if let Ok(film) = Self::parse_film_result(&element) {
+                    if let Ok(film) = Self::parse_film_result(&element, base_url, url) {
                         results.films.push(film);
                     }
                 }
@@ -149,7 +157,7 @@ impl Search {
             Some("reviews") => {
                 let review_selector = Selector::parse(".review").unwrap();
                 for element in dom.select(&review_selector) {
-                    if let Ok(review) = Self::parse_review_result(&element) {
+                    if let Ok(review) = Self::parse_review_result(&element, url) {
                         results.reviews.push(review);
                     }
                 }
@@ -157,7 +165,7 @@ impl Search {
             Some("lists") => {
                 let list_selector = Selector::parse(".list-item").unwrap();
                 for element in dom.select(&list_selector) {
-                    if let Ok(list) = Self::parse_list_result(&element) {
+                    if let Ok(list) = Self::parse_list_result(&element, url) {
                         results.lists.push(list);
                     }
                 }
@@ -165,7 +173,7 @@ impl Search {
             Some("members") => {
                 let member_selector = Selector::parse(".person-summary").unwrap();
                 for element in dom.select(&member_selector) {
-                    if let Ok(member) = Self::parse_member_result(&element) {
+                    if let Ok(member) = Self::parse_member_result(&element, url) {
                         results.members.push(member);
                     }
                 }
@@ -176,7 +184,7 @@ impl Search {
         Ok(results)
     }
 
-    fn parse_film_result(element: &scraper::ElementRef) -> Result<SearchFilm> {
+    fn parse_film_result(element: &scraper::ElementRef, base_url: &str, page_url: &str) -> Result<SearchFilm> {
         use scraper::Selector;
         
         let title_selector = Selector::parse(".film-title a").unwrap();
@@ -184,14 +192,14 @@ impl Search {
         let poster_selector = Selector::parse(".film-poster img").unwrap();
         
         let title_element = element.select(&title_selector).next()
-            .ok_or_else(|| Error::Parse("Film title not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".film-title a", "search result title"))?;
         
         let title = title_element.inner_html();
         let href = title_element.value().attr("href")
-            .ok_or_else(|| Error::Parse("Film URL not found".to_string()))?;
+            .ok_or_else(|| Error::parse(page_url, ".film-title a[href]", "search result URL"))?;
         
         let slug = href.trim_start_matches("/film/").trim_end_matches("/").to_string();
-        let url = format!("{}{}", DOMAIN, href);
+        let url = build_letterboxd_url(base_url, href);
         
         let year = element.select(&year_selector)
             .next()
@@ -213,29 +221,34 @@ impl Search {
         })
     }
 
-    fn parse_review_result(_element: &scraper::ElementRef) -> Result<SearchReview> {
+    fn parse_review_result(_element: &scraper::ElementRef, page_url: &str) -> Result<SearchReview> {
         // TODO: Implement review parsing
-        Err(Error::Parse("Review parsing not implemented".to_string()))
+        Err(Error::parse(page_url, ".review", "review results are not supported yet"))
     }
 
-    fn parse_list_result(_element: &scraper::ElementRef) -> Result<SearchList> {
+    fn parse_list_result(_element: &scraper::ElementRef, page_url: &str) -> Result<SearchList> {
         // TODO: Implement list parsing
-        Err(Error::Parse("List parsing not implemented".to_string()))
+        Err(Error::parse(page_url, ".list-item", "list results are not supported yet"))
     }
 
-    fn parse_member_result(_element: &scraper::ElementRef) -> Result<SearchMember> {
+    fn parse_member_result(_element: &scraper::ElementRef, page_url: &str) -> Result<SearchMember> {
         // TODO: Implement member parsing
-        Err(Error::Parse("Member parsing not implemented".to_string()))
+        Err(Error::parse(page_url, ".person-summary", "member results are not supported yet"))
     }
 
+    /// Fetch pages 2 to `max_pages` and add their results, stopping early at the last page
     pub async fn get_more_results(&mut self, max_pages: u32) -> Result<()> {
-        for page in 2..=max_pages {
-            let client = Client::new();
-            let page_url = format!("{}/page/{}", self.url, page);
-            
-            let dom = client.get_page(&page_url).await?;
-            let page_results = Self::parse_search_results(&dom, self.search_filter.as_deref())?;
-            
+        if !self.has_next || max_pages < 2 {
+            return Ok(());
+        }
+
+        let pages: Vec<SearchResults> = self.result_pages()
+            .start_page(2)
+            .take((max_pages - 1) as usize)
+            .try_collect()
+            .await?;
+
+        for page_results in pages {
             // Merge results
             self.results.films.extend(page_results.films);
             self.results.reviews.extend(page_results.reviews);
@@ -249,4 +262,13 @@ impl Search {
         
         Ok(())
     }
+
+    /// Stream of the results of each page of the search, starting with the first
+    pub fn result_pages(&self) -> Paginator<SearchResults> {
+        let base_url = self.client.base_url().to_string();
+        let search_filter = self.search_filter.clone();
+        Paginator::for_url(&self.client, &self.url, move |dom, page_url| {
+            Ok(vec![Self::parse_search_results(dom, &base_url, page_url, search_filter.as_deref())?])
+        })
+    }
 }
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use regex::Regex;
-use crate::core::{Client, Error, Result, constants::DOMAIN};
-use crate::pages::{UserActivity, UserDiary, UserFilms, UserLikes, UserLists, UserNetwork, UserProfile, UserReviews, UserTags, UserWatchlist};
-use crate::models::{WatchlistMovie, DiaryMovieEntry};
+use crate::core::{Client, Error, Result};
+use crate::pages::{DiaryLogEntry, ProfileDetails, UserActivity, UserDiary, UserFilms, UserLikes, UserLists, UserNetwork, UserProfile, UserReviews, UserTags, UserWatchlist};
+use crate::models::{DiaryMovieEntry, Review, WatchlistMovie};
+use crate::utils::{extract_and_convert_shorthand, extract_film_slug, resize_avatar_url};
+use crate::utils::transform::build_user_url;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
@@ -11,6 +13,8 @@ pub struct User {
     pub url: String,
     pub id: Option<u64>,
     pub is_hq: bool,
+    /// Paid membership shown by the profile badge
+    pub tier: MemberTier,
     pub display_name: String,
     pub bio: Option<String>,
     pub location: Option<String>,
@@ -19,14 +23,31 @@ pub struct User {
     pub stats: Option<UserStats>,
     pub favorites: Option<HashMap<String, FavoriteMovie>>,
     pub avatar: Option<String>,
+    /// The avatar at each size in [`AVATAR_SIZES`], keyed by width in pixels
+    pub avatar_sizes: HashMap<u32, String>,
     pub recent: UserRecent,
+    #[serde(skip)]
+    client: Client,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Square sizes, in pixels, the website serves avatars at
+pub const AVATAR_SIZES: [u32; 5] = [40, 80, 144, 220, 1000];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemberTier {
+    #[default]
+    Free,
+    Pro,
+    Patron,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FavoriteMovie {
     pub name: String,
     pub slug: String,
     pub url: String,
+    #[serde(default)]
+    pub film_id: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,99 +98,210 @@ pub struct UserPages {
 
 impl User {
     pub async fn new(username: &str) -> Result<Self> {
+        Self::new_with_client(&Client::new(), username).await
+    }
+
+    pub async fn new_with_client(client: &Client, username: &str) -> Result<Self> {
         let username_regex = Regex::new(r"^[A-Za-z0-9_]*$").unwrap();
         if !username_regex.is_match(username) {
             return Err(Error::InvalidUsername(username.to_string()));
         }
 
         let username = username.to_lowercase();
-        let client = Client::new();
-        let url = format!("{}/user/{}", DOMAIN, username);
+        let url = build_user_url(client.base_url(), &username);
         
-        let dom = client.get_page(&url).await?;
+        let dom = client.get_page(&url).await.map_err(|e| {
+            if e.is_not_found() {
+                Error::UserNotFound(username.clone())
+            } else {
+                e
+            }
+        })?;
         
         // Extract user data from the HTML
-        let user = Self::parse_user_data(&dom, &username, &url)?;
+        let user = Self::parse_user_data(&dom, client, &username, &url)?;
         
         Ok(user)
     }
 
-    fn parse_user_data(dom: &scraper::Html, username: &str, url: &str) -> Result<Self> {
+    fn parse_user_data(dom: &scraper::Html, client: &Client, username: &str, url: &str) -> Result<Self> {
         use scraper::Selector;
         
         let display_name_selector = Selector::parse("h1.title-1").unwrap();
-        let bio_selector = Selector::parse(".profile-summary .bio").unwrap();
-        let location_selector = Selector::parse(".profile-summary .location").unwrap();
-        let website_selector = Selector::parse(".profile-summary .website").unwrap();
-        let stats_selector = Selector::parse(".profile-stats li").unwrap();
+        let avatar_selector = Selector::parse(".profile-avatar img").unwrap();
+        let report_selector = Selector::parse("[data-report-url]").unwrap();
+        let watchlist_count_selector = Selector::parse(".watchlist-aside a.all-link").unwrap();
         
         let display_name = dom.select(&display_name_selector)
             .next()
             .map(|el| el.inner_html())
             .unwrap_or_else(|| username.to_string());
 
-        let bio = dom.select(&bio_selector)
-            .next()
-            .map(|el| el.inner_html());
+        let details = ProfileDetails::parse(dom, client);
 
-        let location = dom.select(&location_selector)
+        // The member id only appears in the report link, e.g. /ajax/member:12345/report-form/
+        let member_id_regex = Regex::new(r"member:(\d+)").unwrap();
+        let id = dom.select(&report_selector)
+            .filter_map(|el| el.value().attr("data-report-url"))
+            .find_map(|report_url| member_id_regex.captures(report_url))
+            .and_then(|caps| caps[1].parse().ok());
+
+        let avatar = dom.select(&avatar_selector)
             .next()
-            .map(|el| el.inner_html());
+            .and_then(|el| el.value().attr("src"))
+            .map(str::to_string);
+        let avatar_sizes = avatar.as_deref()
+            .map(|avatar| AVATAR_SIZES.iter().map(|&size| (size, resize_avatar_url(avatar, size))).collect())
+            .unwrap_or_default();
 
-        let website = dom.select(&website_selector)
+        let watchlist_length = dom.select(&watchlist_count_selector)
             .next()
-            .map(|el| el.inner_html());
-
-        // Parse stats
-        let stats_elements: Vec<_> = dom.select(&stats_selector).collect();
-        let stats = if !stats_elements.is_empty() {
-            Some(UserStats {
-                films: 0,       // TODO: Parse from stats
-                this_year: 0,
-                following: 0,
-                followers: 0,
-                lists: 0,
-                reviews: 0,
-                diary_entries: 0,
-            })
-        } else {
-            None
-        };
+            .map(|el| extract_and_convert_shorthand(&el.text().collect::<String>()));
 
         Ok(User {
             username: username.to_string(),
             url: url.to_string(),
-            id: None, // TODO: Extract user ID
-            is_hq: false, // TODO: Detect HQ status
+            id,
+            is_hq: details.is_hq,
+            tier: details.tier,
             display_name,
-            bio,
-            location,
-            website,
-            watchlist_length: None,
-            stats,
-            favorites: None,
-            avatar: None,
+            bio: details.bio_html,
+            location: details.location,
+            website: details.website,
+            watchlist_length,
+            stats: Self::parse_stats(dom),
+            favorites: (!details.favorites.is_empty()).then(|| {
+                details.favorites
+                    .into_iter()
+                    .map(|favorite| (favorite.film_id.map(|id| id.to_string()).unwrap_or_else(|| favorite.slug.clone()), favorite))
+                    .collect()
+            }),
+            avatar,
+            avatar_sizes,
             recent: UserRecent {
-                watchlist: Vec::new(),
-                diary: DiaryData {
-                    months: HashMap::new(),
-                },
+                watchlist: Self::parse_recent_watchlist(dom),
+                diary: Self::parse_recent_diary(dom),
             },
+            client: client.clone(),
         })
     }
 
+    /// Counts from the statistics in the profile header; counts the page does not show are zero
+    fn parse_stats(dom: &scraper::Html) -> Option<UserStats> {
+        use scraper::Selector;
+
+        let statistic_selector = Selector::parse(".profile-stats .profile-statistic").unwrap();
+        let value_selector = Selector::parse(".value").unwrap();
+        let definition_selector = Selector::parse(".definition").unwrap();
+
+        let mut stats = UserStats {
+            films: 0,
+            this_year: 0,
+            following: 0,
+            followers: 0,
+            lists: 0,
+            reviews: 0,
+            diary_entries: 0,
+        };
+        let mut found = false;
+
+        for statistic in dom.select(&statistic_selector) {
+            let value = statistic.select(&value_selector).next().map(|el| el.text().collect::<String>());
+            let definition = statistic.select(&definition_selector).next().map(|el| el.text().collect::<String>());
+            let (Some(value), Some(definition)) = (value, definition) else {
+                continue;
+            };
+
+            let count = extract_and_convert_shorthand(&value);
+            let field = match definition.trim().to_lowercase().as_str() {
+                "film" | "films" => &mut stats.films,
+                "this year" => &mut stats.this_year,
+                "following" => &mut stats.following,
+                "follower" | "followers" => &mut stats.followers,
+                "list" | "lists" => &mut stats.lists,
+                "review" | "reviews" => &mut stats.reviews,
+                "diary entry" | "diary entries" => &mut stats.diary_entries,
+                _ => continue,
+            };
+            *field = count;
+            found = true;
+        }
+
+        found.then_some(stats)
+    }
+
+    /// Slugs of the films shown in the watchlist sidebar
+    fn parse_recent_watchlist(dom: &scraper::Html) -> Vec<String> {
+        use scraper::Selector;
+
+        let poster_selector = Selector::parse(".watchlist-aside .film-poster").unwrap();
+        dom.select(&poster_selector)
+            .filter_map(|poster| poster.value().attr("data-film-slug"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Entries of the diary sidebar, grouped by month and then day
+    fn parse_recent_diary(dom: &scraper::Html) -> DiaryData {
+        use scraper::Selector;
+
+        let month_selector = Selector::parse(".diary-aside .listitem").unwrap();
+        let month_name_selector = Selector::parse("h3").unwrap();
+        let day_selector = Selector::parse("dt, dd").unwrap();
+        let link_selector = Selector::parse("a").unwrap();
+
+        let mut months: HashMap<String, HashMap<String, Vec<DiaryEntry>>> = HashMap::new();
+        for month in dom.select(&month_selector) {
+            let Some(month_name) = month.select(&month_name_selector).next() else {
+                continue;
+            };
+            let days = months.entry(month_name.text().collect::<String>().trim().to_string()).or_default();
+
+            // Each day is a <dt> followed by the <dd> entries logged on it
+            let mut day = None;
+            for el in month.select(&day_selector) {
+                if el.value().name() == "dt" {
+                    day = Some(el.text().collect::<String>().trim().to_string());
+                    continue;
+                }
+                let (Some(day), Some(link)) = (&day, el.select(&link_selector).next()) else {
+                    continue;
+                };
+                let Some(slug) = link.value().attr("href").and_then(extract_film_slug) else {
+                    continue;
+                };
+
+                days.entry(day.clone()).or_default().push(DiaryEntry {
+                    name: link.text().collect::<String>().trim().to_string(),
+                    slug,
+                    rating: None,
+                    review: None,
+                    liked: false,
+                    rewatch: false,
+                });
+            }
+        }
+
+        DiaryData { months }
+    }
+
+    /// The client this user was fetched with, shared by all of its pages
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
     pub fn pages(&self) -> UserPages {
         UserPages {
-            activity: UserActivity::new(&self.username),
-            diary: UserDiary::new(&self.username),
-            films: UserFilms::new(&self.username),
-            likes: UserLikes::new(&self.username),
-            lists: UserLists::new(&self.username),
-            network: UserNetwork::new(&self.username),
-            profile: UserProfile::new(&self.username),
-            reviews: UserReviews::new(&self.username),
-            tags: UserTags::new(&self.username),
-            watchlist: UserWatchlist::new(&self.username),
+            activity: UserActivity::new_with_client(&self.client, &self.username),
+            diary: UserDiary::new_with_client(&self.client, &self.username),
+            films: UserFilms::new_with_client(&self.client, &self.username),
+            likes: UserLikes::new_with_client(&self.client, &self.username),
+            lists: UserLists::new_with_client(&self.client, &self.username),
+            network: UserNetwork::new_with_client(&self.client, &self.username),
+            profile: UserProfile::new_with_client(&self.client, &self.username),
+            reviews: UserReviews::new_with_client(&self.client, &self.username),
+            tags: UserTags::new_with_client(&self.client, &self.username),
+            watchlist: UserWatchlist::new_with_client(&self.client, &self.username),
         }
     }
 
@@ -177,7 +309,7 @@ impl User {
         self.pages().activity.get_activity().await
     }
 
-    pub async fn get_diary(&self, year: Option<i32>, month: Option<u32>, day: Option<u32>, page: Option<u32>) -> Result<HashMap<String, serde_json::Value>> {
+    pub async fn get_diary(&self, year: Option<i32>, month: Option<u32>, day: Option<u32>, page: Option<u32>) -> Result<Vec<DiaryLogEntry>> {
         self.pages().diary.get_diary(year, month, day, page).await
     }
 
@@ -201,7 +333,7 @@ impl User {
         self.pages().likes.get_liked_films().await
     }
 
-    pub async fn get_liked_reviews(&self) -> Result<HashMap<String, serde_json::Value>> {
+    pub async fn get_liked_reviews(&self) -> Result<Vec<Review>> {
         self.pages().likes.get_liked_reviews().await
     }
 
@@ -217,7 +349,7 @@ impl User {
         self.pages().network.get_following().await
     }
 
-    pub async fn get_reviews(&self) -> Result<HashMap<String, serde_json::Value>> {
+    pub async fn get_reviews(&self) -> Result<Vec<Review>> {
         self.pages().reviews.get_reviews().await
     }
 
@@ -10,12 +10,63 @@ pub mod user_tags;
 pub mod user_watchlist;
 
 pub use user_activity::UserActivity;
-pub use user_diary::UserDiary;
+pub use user_diary::{DiaryLogEntry, DiaryPage, UserDiary};
 pub use user_films::UserFilms;
 pub use user_likes::UserLikes;
 pub use user_lists::UserLists;
 pub use user_network::UserNetwork;
-pub use user_profile::UserProfile;
+pub use user_profile::{ProfileDetails, RecentFilm, SocialLink, UserProfile};
 pub use user_reviews::UserReviews;
 pub use user_tags::UserTags;
 pub use user_watchlist::UserWatchlist;
+
+use std::collections::HashMap;
+use futures::TryStreamExt;
+use scraper::{Html, Selector};
+use serde_json::json;
+use crate::core::{Paginator, Result};
+use crate::utils::parser::parse_rating_class;
+use crate::utils::transform::build_film_url;
+
+/// Films of a poster grid, each with `name`, `slug`, `url`, `film_id`, `rating` and `liked`
+pub(crate) fn parse_poster_films(dom: &Html, base_url: &str) -> Vec<serde_json::Value> {
+    let container_selector = Selector::parse("li.poster-container").unwrap();
+    let poster_selector = Selector::parse(".film-poster[data-film-slug]").unwrap();
+    let img_selector = Selector::parse("img").unwrap();
+    let rating_selector = Selector::parse(".poster-viewingdata .rating").unwrap();
+    let liked_selector = Selector::parse(".poster-viewingdata .icon-liked").unwrap();
+
+    dom.select(&container_selector)
+        .filter_map(|container| {
+            let poster = container.select(&poster_selector).next()?;
+            let slug = poster.value().attr("data-film-slug")?;
+            let name = poster.value().attr("data-film-name")
+                .or_else(|| poster.select(&img_selector).next().and_then(|img| img.value().attr("alt")))
+                .unwrap_or(slug);
+
+            Some(json!({
+                "name": name,
+                "slug": slug,
+                "url": build_film_url(base_url, slug),
+                "film_id": poster.value().attr("data-film-id").and_then(|id| id.parse::<u64>().ok()),
+                "rating": container.select(&rating_selector)
+                    .next()
+                    .and_then(|el| el.value().attr("class"))
+                    .and_then(parse_rating_class),
+                "liked": container.select(&liked_selector).next().is_some(),
+            }))
+        })
+        .collect()
+}
+
+/// Every item of a stream, keyed by one of its string fields
+pub(crate) async fn collect_by_key(items: Paginator<serde_json::Value>, key: &str) -> Result<HashMap<String, serde_json::Value>> {
+    items
+        .try_fold(HashMap::new(), |mut map, item| async move {
+            if let Some(value) = item[key].as_str().map(str::to_string) {
+                map.insert(value, item);
+            }
+            Ok(map)
+        })
+        .await
+}
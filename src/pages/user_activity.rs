@@ -1,31 +1,36 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use crate::core::{Client, Result};
+use crate::utils::transform::{build_user_url, build_user_section_url};
 
 #[derive(Debug)]
 pub struct UserActivity {
     username: String,
+    client: Client,
 }
 
 impl UserActivity {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
     pub async fn get_activity(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
+        let url = build_user_url(self.client.base_url(), &self.username);
+        let _dom = self.client.get_page(&url).await?;
         
         // TODO: Parse user activity from the page
         Ok(HashMap::new())
     }
 
     pub async fn get_activity_following(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/following/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
+        let url = build_user_section_url(self.client.base_url(), &self.username, "following");
+        let _dom = self.client.get_page(&url).await?;
         
         // TODO: Parse following activity from the page
         Ok(HashMap::new())
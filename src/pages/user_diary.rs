@@ -1,140 +1,141 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use futures::TryStreamExt;
+use crate::core::{Client, Error, Paginator, Result};
+use crate::core::paginator::{has_next_page, page_url};
+use crate::utils::parser::{clean_text, extract_film_slug, parse_rating_class};
+use crate::utils::transform::{build_diary_url, build_user_section_url};
 use crate::models::{DiaryMovieEntry, Movie};
-use scraper::Selector;
 
 #[derive(Debug)]
 pub struct UserDiary {
     username: String,
+    client: Client,
+}
+
+/// One page of a diary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiaryPage {
+    pub page: u32,
+    pub entries: Vec<DiaryLogEntry>,
+    pub has_next: bool,
+}
+
+/// A film logged in a diary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiaryLogEntry {
+    /// Viewing id of the entry
+    pub entry_id: Option<u64>,
+    pub date: NaiveDate,
+    pub film_slug: String,
+    pub film_title: String,
+    pub film_year: Option<u16>,
+    pub film_id: Option<u64>,
+    pub rating: Option<f32>,
+    pub liked: bool,
+    pub rewatch: bool,
+    pub has_review: bool,
+    /// The entry's review page, when it has a review
+    pub review_url: Option<String>,
+    pub tags: Vec<String>,
 }
 
 impl UserDiary {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
-    pub async fn get_diary(&self, year: Option<i32>, month: Option<u32>, day: Option<u32>, page: Option<u32>) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        
-        let mut url = format!("{}/{}/films/diary/", DOMAIN, self.username);
-        
-        if let Some(year) = year {
-            url.push_str(&format!("for/{}/", year));
-            
-            if let Some(month) = month {
-                url.push_str(&format!("{:02}/", month));
-                
-                if let Some(day) = day {
-                    url.push_str(&format!("{:02}/", day));
-                }
-            }
-        }
-        
+    /// Diary entries for a year, month or day, or the whole diary.
+    ///
+    /// With `page` only that page is fetched; otherwise every page of the
+    /// range is, in the order the diary shows them (most recent first).
+    /// See [`UserDiary::entries`] to stream them instead.
+    pub async fn get_diary(&self, year: Option<i32>, month: Option<u32>, day: Option<u32>, page: Option<u32>) -> Result<Vec<DiaryLogEntry>> {
         if let Some(page) = page {
-            url.push_str(&format!("page/{}/", page));
+            return Ok(self.get_diary_page(year, month, day, page).await?.entries);
         }
-        
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse diary entries from the page
-        Ok(HashMap::new())
+
+        self.entries(year, month, day).try_collect().await
+    }
+
+    /// Stream of the diary entries for a year, month or day, or the whole diary, most recent first
+    pub fn entries(&self, year: Option<i32>, month: Option<u32>, day: Option<u32>) -> Paginator<DiaryLogEntry> {
+        let url = build_diary_url(self.client.base_url(), &self.username, year, month, day);
+        let client = self.client.clone();
+        Paginator::for_url(&self.client, &url, move |dom, url| parse_diary_entries(dom, &client, url))
+    }
+
+    /// A single page of diary entries for a year, month or day, or the whole diary
+    pub async fn get_diary_page(&self, year: Option<i32>, month: Option<u32>, day: Option<u32>, page: u32) -> Result<DiaryPage> {
+        let url = page_url(&build_diary_url(self.client.base_url(), &self.username, year, month, day), page);
+        let dom = self.client.get_page(&url).await?;
+
+        Ok(DiaryPage {
+            page,
+            entries: parse_diary_entries(&dom, &self.client, &url)?,
+            has_next: has_next_page(&dom),
+        })
     }
 
-    pub async fn get_year(&self, year: i32) -> Result<HashMap<String, serde_json::Value>> {
+    pub async fn get_year(&self, year: i32) -> Result<Vec<DiaryLogEntry>> {
         self.get_diary(Some(year), None, None, None).await
     }
 
-    pub async fn get_month(&self, year: i32, month: u32) -> Result<HashMap<String, serde_json::Value>> {
+    pub async fn get_month(&self, year: i32, month: u32) -> Result<Vec<DiaryLogEntry>> {
         self.get_diary(Some(year), Some(month), None, None).await
     }
 
-    pub async fn get_day(&self, year: i32, month: u32, day: u32) -> Result<HashMap<String, serde_json::Value>> {
+    pub async fn get_day(&self, year: i32, month: u32, day: u32) -> Result<Vec<DiaryLogEntry>> {
         self.get_diary(Some(year), Some(month), Some(day), None).await
     }
 
     pub async fn get_wrapped(&self, year: i32) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/films/diary/for/{}/wrapped/", DOMAIN, self.username, year);
-        let _dom = client.get_page(&url).await?;
+        let url = build_user_section_url(self.client.base_url(), &self.username, &format!("films/diary/for/{}/wrapped", year));
+        let _dom = self.client.get_page(&url).await?;
         
         // TODO: Parse wrapped data from the page
         Ok(HashMap::new())
     }
     
     pub async fn get_diary_entries(&self) -> Result<Vec<DiaryMovieEntry>> {
-        let client = Client::new();
-        let url = format!("{}/{}/films/diary/", DOMAIN, self.username);
-        let dom = client.get_page(&url).await?;
-        
-        let mut entries = Vec::new();
-        
-        // Parse diary entries from the HTML
-        let entry_selector = Selector::parse("tr.diary-entry-row").unwrap();
-        let film_selector = Selector::parse("h3.headline-3 a").unwrap();
-        let date_selector = Selector::parse("td.td-calendar-date a").unwrap();
-        
-        for entry in dom.select(&entry_selector) {
-            if let Some(film_link) = entry.select(&film_selector).next() {
-                let name = film_link.inner_html();
-                let href = film_link.value().attr("href").unwrap_or("");
-                let slug = href.trim_start_matches("/film/").trim_end_matches('/').to_string();
-                
-                // Extract date if available
-                let (month, day) = if let Some(date_elem) = entry.select(&date_selector).next() {
-                    if let Some(datetime) = date_elem.value().attr("data-date") {
-                        // Parse date format: "2024-03-15"
-                        let parts: Vec<&str> = datetime.split('-').collect();
-                        if parts.len() >= 3 {
-                            let month = parts[1].parse::<u32>().unwrap_or(1);
-                            let day = parts[2].parse::<u32>().unwrap_or(1);
-                            (month, day)
-                        } else {
-                            (1, 1)
-                        }
-                    } else {
-                        (1, 1)
-                    }
-                } else {
-                    (1, 1)
-                };
-                
-                // Try to get movie details
-                let movie_entry = DiaryMovieEntry {
-                    name: name.clone(),
-                    slug: slug.clone(),
-                    title: name,
-                    year: None,
-                    director: None,
-                    genres: Vec::new(),
-                    runtime: None,
-                    rating: None,
-                    description: None,
-                    month,
-                    day,
-                };
-                
-                entries.push(movie_entry);
-            }
-        }
+        let mut entries: Vec<DiaryMovieEntry> = self.get_diary(None, None, None, None)
+            .await?
+            .into_iter()
+            .map(|entry| DiaryMovieEntry {
+                name: entry.film_title.clone(),
+                slug: entry.film_slug,
+                title: entry.film_title,
+                year: entry.film_year,
+                director: None,
+                genres: Vec::new(),
+                runtime: None,
+                rating: None,
+                description: None,
+                month: entry.date.month(),
+                day: entry.date.day(),
+                date: Some(entry.date),
+            })
+            .collect();
         
         // Enrich with movie details (limit to first 10 for performance)
         for entry in entries.iter_mut().take(10) {
-            if let Ok(movie) = Movie::new(&entry.slug).await {
+            if let Ok(movie) = Movie::new_with_client(&self.client, &entry.slug).await {
                 entry.title = movie.title.clone();
                 entry.year = movie.year;
-                entry.director = movie.crew.get("director")
-                    .and_then(|dirs| dirs.first())
-                    .and_then(|d| d.get("name"))
-                    .map(|n| n.to_string());
-                entry.genres = movie.genres.iter()
-                    .filter(|g| g.get("type").and_then(|t| t.as_str()) == Some("genre"))
-                    .filter_map(|g| g.get("name").and_then(|n| n.as_str()))
-                    .map(String::from)
-                    .collect();
-                entry.runtime = movie.runtime.and_then(|r| r.parse().ok());
-                entry.rating = movie.rating.and_then(|r| r.parse().ok());
+                entry.director = movie.crew.directors().first().map(|d| d.name.clone());
+                entry.genres = movie.genres.iter().map(|g| g.name.clone()).collect();
+                entry.runtime = movie.runtime_minutes.and_then(|r| u16::try_from(r).ok());
+                entry.rating = movie.average_rating;
                 entry.description = movie.description.clone();
             }
         }
@@ -142,3 +143,82 @@ impl UserDiary {
         Ok(entries)
     }
 }
+
+fn parse_diary_entries(dom: &Html, client: &Client, url: &str) -> Result<Vec<DiaryLogEntry>> {
+    let row_selector = Selector::parse("tr.diary-entry-row").unwrap();
+    dom.select(&row_selector)
+        .map(|row| parse_diary_entry(row, client, url))
+        .collect()
+}
+
+fn parse_diary_entry(row: ElementRef, client: &Client, url: &str) -> Result<DiaryLogEntry> {
+    let film_selector = Selector::parse(".td-film-details h3 a").unwrap();
+    let poster_selector = Selector::parse(".film-poster").unwrap();
+    let released_selector = Selector::parse(".td-released").unwrap();
+    let rating_selector = Selector::parse(".td-rating .rating").unwrap();
+    let liked_selector = Selector::parse(".td-like .icon-liked").unwrap();
+    let rewatch_selector = Selector::parse(".td-rewatch").unwrap();
+    let review_selector = Selector::parse(".td-review a[href]").unwrap();
+    let tag_selector = Selector::parse(".tags a").unwrap();
+
+    let film_link = row.select(&film_selector).next();
+    let poster = row.select(&poster_selector).next();
+
+    let film_slug = poster
+        .and_then(|poster| poster.value().attr("data-film-slug"))
+        .map(str::to_string)
+        .or_else(|| film_link.and_then(|a| a.value().attr("href")).and_then(extract_film_slug))
+        .ok_or_else(|| Error::parse(url, ".td-film-details h3 a", "diary entry film"))?;
+
+    let date = parse_entry_date(row)
+        .ok_or_else(|| Error::parse(url, ".td-day a", format!("diary date of {}", film_slug)))?;
+
+    let review_url = row.select(&review_selector)
+        .next()
+        .and_then(|a| a.value().attr("href"))
+        .map(|href| client.url(href));
+
+    Ok(DiaryLogEntry {
+        entry_id: row.value().attr("data-viewing-id").and_then(|id| id.parse().ok()),
+        date,
+        film_title: film_link
+            .map(|a| clean_text(&a.text().collect::<String>()))
+            .unwrap_or_else(|| film_slug.clone()),
+        film_year: row.select(&released_selector)
+            .next()
+            .and_then(|el| el.text().collect::<String>().trim().parse().ok()),
+        film_id: poster
+            .and_then(|poster| poster.value().attr("data-film-id"))
+            .and_then(|id| id.parse().ok()),
+        film_slug,
+        rating: row.select(&rating_selector)
+            .next()
+            .and_then(|el| el.value().attr("class"))
+            .and_then(parse_rating_class),
+        liked: row.select(&liked_selector).next().is_some(),
+        // Rows that aren't rewatches have the icon switched off
+        rewatch: row.select(&rewatch_selector)
+            .next()
+            .is_some_and(|el| !el.value().has_class("icon-status-off", scraper::CaseSensitivity::CaseSensitive)),
+        has_review: review_url.is_some(),
+        review_url,
+        tags: row.select(&tag_selector)
+            .map(|a| clean_text(&a.text().collect::<String>()))
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+    })
+}
+
+/// The viewing date, from the day link (`/films/diary/for/2024/03/15/`) or its `data-date`
+fn parse_entry_date(row: ElementRef) -> Option<NaiveDate> {
+    let day_selector = Selector::parse(".td-day a, .td-calendar-date a").unwrap();
+    let day_link = row.select(&day_selector).next()?;
+
+    if let Some(date) = day_link.value().attr("data-date") {
+        return NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+    }
+
+    let date_regex = Regex::new(r"/for/(\d{4})/(\d{2})/(\d{2})/").unwrap();
+    let caps = date_regex.captures(day_link.value().attr("href")?)?;
+    NaiveDate::from_ymd_opt(caps[1].parse().ok()?, caps[2].parse().ok()?, caps[3].parse().ok()?)
+}
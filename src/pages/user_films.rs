@@ -1,56 +1,60 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use crate::core::{Client, Paginator, Result};
+use crate::pages::{collect_by_key, parse_poster_films};
+use crate::utils::transform::build_user_section_url;
 
 #[derive(Debug)]
 pub struct UserFilms {
     username: String,
+    client: Client,
 }
 
 impl UserFilms {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
+    /// Every film watched, keyed by slug
     pub async fn get_films(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/films/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse user films from the page
-        Ok(HashMap::new())
+        collect_by_key(self.films(), "slug").await
+    }
+
+    /// Stream of the films watched, each with `name`, `slug`, `url`, `film_id`, `rating` and `liked`
+    pub fn films(&self) -> Paginator<serde_json::Value> {
+        self.poster_films("films")
     }
 
     pub async fn get_films_rated(&self, rating: f32) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
         let rating_str = if rating.fract() == 0.0 {
             format!("{}", rating as i32)
         } else {
             format!("{}", rating)
         };
-        let url = format!("{}/{}/films/rated/{}/", DOMAIN, self.username, rating_str);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse rated films from the page
-        Ok(HashMap::new())
+        collect_by_key(self.poster_films(&format!("films/rated/{}", rating_str)), "slug").await
     }
 
     pub async fn get_films_not_rated(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/films/not-rated/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse unrated films from the page
-        Ok(HashMap::new())
+        collect_by_key(self.poster_films("films/not-rated"), "slug").await
     }
 
     pub async fn get_genre_info(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/films/genres/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
+        let url = build_user_section_url(self.client.base_url(), &self.username, "films/genres");
+        let _dom = self.client.get_page(&url).await?;
         
         // TODO: Parse genre statistics from the page
         Ok(HashMap::new())
     }
+
+    fn poster_films(&self, section: &str) -> Paginator<serde_json::Value> {
+        let url = build_user_section_url(self.client.base_url(), &self.username, section);
+        let base_url = self.client.base_url().to_string();
+        Paginator::for_url(&self.client, &url, move |dom, _| Ok(parse_poster_films(dom, &base_url)))
+    }
 }
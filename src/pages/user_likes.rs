@@ -1,33 +1,47 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use futures::TryStreamExt;
+use crate::core::{Client, Paginator, Result};
+use crate::models::review::{review_listing, Review};
+use crate::pages::{collect_by_key, parse_poster_films};
+use crate::utils::transform::build_user_section_url;
 
 #[derive(Debug)]
 pub struct UserLikes {
     username: String,
+    client: Client,
 }
 
 impl UserLikes {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
+    /// Every liked film, keyed by slug
     pub async fn get_liked_films(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/likes/films/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse liked films from the page
-        Ok(HashMap::new())
+        collect_by_key(self.liked_films(), "slug").await
+    }
+
+    /// Every liked review, most recently liked first
+    pub async fn get_liked_reviews(&self) -> Result<Vec<Review>> {
+        self.liked_reviews().try_collect().await
+    }
+
+    /// Stream of the liked films, each with `name`, `slug`, `url`, `film_id`, `rating` and `liked`
+    pub fn liked_films(&self) -> Paginator<serde_json::Value> {
+        let url = build_user_section_url(self.client.base_url(), &self.username, "likes/films");
+        let base_url = self.client.base_url().to_string();
+        Paginator::for_url(&self.client, &url, move |dom, _| Ok(parse_poster_films(dom, &base_url)))
     }
 
-    pub async fn get_liked_reviews(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/likes/reviews/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse liked reviews from the page
-        Ok(HashMap::new())
+    /// Stream of the liked reviews, page by page
+    pub fn liked_reviews(&self) -> Paginator<Review> {
+        review_listing(&self.client, &build_user_section_url(self.client.base_url(), &self.username, "likes/reviews"))
     }
 }
@@ -1,24 +1,67 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use scraper::{Html, Selector};
+use serde_json::json;
+use crate::core::{Client, Paginator, Result};
+use crate::pages::collect_by_key;
+use crate::utils::parser::{clean_text, extract_numeric_text};
+use crate::utils::transform::build_user_section_url;
 
 #[derive(Debug)]
 pub struct UserLists {
     username: String,
+    client: Client,
 }
 
 impl UserLists {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
+    /// Every list, keyed by slug
     pub async fn get_lists(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/lists/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse user lists from the page
-        Ok(HashMap::new())
+        collect_by_key(self.lists(), "slug").await
+    }
+
+    /// Stream of the lists, each with `name`, `slug`, `url`, `list_id`, `film_count` and `description`
+    pub fn lists(&self) -> Paginator<serde_json::Value> {
+        let url = build_user_section_url(self.client.base_url(), &self.username, "lists");
+        let client = self.client.clone();
+        Paginator::for_url(&self.client, &url, move |dom, _| Ok(parse_lists(dom, &client)))
     }
 }
+
+fn parse_lists(dom: &Html, client: &Client) -> Vec<serde_json::Value> {
+    let list_selector = Selector::parse(".list-set .list").unwrap();
+    let title_selector = Selector::parse(".title a[href]").unwrap();
+    let count_selector = Selector::parse(".value").unwrap();
+    let description_selector = Selector::parse(".body-text").unwrap();
+
+    dom.select(&list_selector)
+        .filter_map(|list| {
+            let title = list.select(&title_selector).next()?;
+            let href = title.value().attr("href")?;
+            // List URLs look like /alice/list/favourite-heists/
+            let slug = href.trim_matches('/').rsplit('/').next()?.to_string();
+
+            Some(json!({
+                "name": clean_text(&title.text().collect::<String>()),
+                "slug": slug,
+                "url": client.url(href),
+                "list_id": list.value().attr("data-film-list-id").and_then(|id| id.parse::<u64>().ok()),
+                "film_count": list.select(&count_selector)
+                    .next()
+                    .and_then(|el| extract_numeric_text(&el.text().collect::<String>())),
+                "description": list.select(&description_selector)
+                    .next()
+                    .map(|el| clean_text(&el.text().collect::<String>())),
+            }))
+        })
+        .collect()
+}
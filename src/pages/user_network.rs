@@ -1,33 +1,76 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use scraper::{Html, Selector};
+use serde_json::json;
+use crate::core::{Client, Paginator, Result};
+use crate::pages::collect_by_key;
+use crate::utils::transform::{build_user_section_url, build_user_url};
 
 #[derive(Debug)]
 pub struct UserNetwork {
     username: String,
+    client: Client,
 }
 
 impl UserNetwork {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
     pub async fn get_followers(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/followers/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse followers from the page
-        Ok(HashMap::new())
+        collect_by_key(self.followers(), "username").await
     }
 
     pub async fn get_following(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/following/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse following from the page
-        Ok(HashMap::new())
+        collect_by_key(self.following(), "username").await
+    }
+
+    /// Stream of the members following this user, each with `username`, `display_name` and `url`
+    pub fn followers(&self) -> Paginator<serde_json::Value> {
+        self.members("followers")
     }
+
+    /// Stream of the members this user follows, each with `username`, `display_name` and `url`
+    pub fn following(&self) -> Paginator<serde_json::Value> {
+        self.members("following")
+    }
+
+    fn members(&self, section: &str) -> Paginator<serde_json::Value> {
+        let url = build_user_section_url(self.client.base_url(), &self.username, section);
+        let base_url = self.client.base_url().to_string();
+        Paginator::for_url(&self.client, &url, move |dom, _| Ok(parse_members(dom, &base_url)))
+    }
+}
+
+fn parse_members(dom: &Html, base_url: &str) -> Vec<serde_json::Value> {
+    let person_selector = Selector::parse("table.person-table tr .person-summary").unwrap();
+    let avatar_selector = Selector::parse("a.avatar").unwrap();
+    let name_selector = Selector::parse("a.name").unwrap();
+
+    dom.select(&person_selector)
+        .filter_map(|person| {
+            let username = person.select(&avatar_selector)
+                .next()
+                .and_then(|a| a.value().attr("href"))
+                .map(|href| href.trim_matches('/').to_string())
+                .filter(|username| !username.is_empty())?;
+
+            let display_name = person.select(&name_selector)
+                .next()
+                .map(|a| a.text().collect::<String>().trim().to_string())
+                .unwrap_or_else(|| username.clone());
+
+            Some(json!({
+                "username": username,
+                "display_name": display_name,
+                "url": build_user_url(base_url, &username),
+            }))
+        })
+        .collect()
 }
@@ -1,24 +1,222 @@
-use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use chrono::NaiveDate;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use crate::core::{Client, Result};
+use crate::models::{FavoriteMovie, MemberTier, Review};
+use crate::models::review::parse_review;
+use crate::utils::parser::{clean_text, parse_rating_class};
+use crate::utils::transform::build_user_url;
 
 #[derive(Debug)]
 pub struct UserProfile {
     username: String,
+    client: Client,
+}
+
+/// Everything shown about a member on their profile page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileDetails {
+    /// Bio as the page renders it, with links and formatting
+    pub bio_html: Option<String>,
+    /// Bio as plain text
+    pub bio: Option<String>,
+    pub location: Option<String>,
+    pub website: Option<String>,
+    pub pronouns: Option<String>,
+    pub social_links: Vec<SocialLink>,
+    /// Only shown by some profiles; the day is the 1st when the page gives a month
+    pub join_date: Option<NaiveDate>,
+    /// Favourite films, in the order shown
+    pub favorites: Vec<FavoriteMovie>,
+    pub pinned_reviews: Vec<Review>,
+    /// Films in the "Recent activity" section, most recent first
+    pub recent_activity: Vec<RecentFilm>,
+    pub tier: MemberTier,
+    pub is_hq: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SocialLink {
+    /// Name of the site, e.g. "twitter" or "instagram"
+    pub service: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentFilm {
+    pub name: String,
+    pub slug: String,
+    pub rating: Option<f32>,
+    pub liked: bool,
+    pub reviewed: bool,
 }
 
 impl UserProfile {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
+        }
+    }
+
+    pub async fn get_profile(&self) -> Result<ProfileDetails> {
+        let url = build_user_url(self.client.base_url(), &self.username);
+        let dom = self.client.get_page(&url).await?;
+
+        Ok(ProfileDetails::parse(&dom, &self.client))
+    }
+}
+
+impl ProfileDetails {
+    pub(crate) fn parse(dom: &Html, client: &Client) -> Self {
+        let bio_element = select_first(dom, ".profile-summary .bio");
+
+        Self {
+            bio_html: bio_element.map(|el| el.inner_html().trim().to_string()),
+            bio: bio_element.map(|el| clean_text(&el.text().collect::<String>())),
+            location: select_text(dom, ".profile-summary .location"),
+            website: select_text(dom, ".profile-summary .website"),
+            pronouns: select_text(dom, ".profile-summary .pronouns"),
+            social_links: parse_social_links(dom),
+            join_date: select_text(dom, ".profile-summary .joined").and_then(|text| parse_join_date(&text)),
+            favorites: parse_favorites(dom, client),
+            pinned_reviews: parse_pinned_reviews(dom, client),
+            recent_activity: parse_recent_activity(dom),
+            tier: if has_badge(dom, "-patron") {
+                MemberTier::Patron
+            } else if has_badge(dom, "-pro") {
+                MemberTier::Pro
+            } else {
+                MemberTier::Free
+            },
+            is_hq: has_badge(dom, "-hq"),
         }
     }
+}
+
+fn select_first<'a>(dom: &'a Html, selector: &str) -> Option<ElementRef<'a>> {
+    dom.select(&Selector::parse(selector).unwrap()).next()
+}
+
+fn select_text(dom: &Html, selector: &str) -> Option<String> {
+    select_first(dom, selector)
+        .map(|el| clean_text(&el.text().collect::<String>()))
+        .filter(|text| !text.is_empty())
+}
+
+fn has_badge(dom: &Html, class: &str) -> bool {
+    select_first(dom, &format!(".profile-summary .badge.{}", class)).is_some()
+}
+
+fn parse_social_links(dom: &Html) -> Vec<SocialLink> {
+    let selector = Selector::parse(".profile-summary .social-links a[href]").unwrap();
+    dom.select(&selector)
+        .filter_map(|link| {
+            let url = link.value().attr("href")?;
+            Some(SocialLink {
+                service: social_service(url)?,
+                url: url.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The site a social link points at, named after its domain ("x.com" counts as twitter)
+fn social_service(url: &str) -> Option<String> {
+    let host = url::Url::parse(url).ok()?.host_str()?.trim_start_matches("www.").to_lowercase();
+    let name = host.rsplit_once('.').map_or(host.as_str(), |(name, _)| name);
+    Some(if name == "x" { "twitter".to_string() } else { name.to_string() })
+}
+
+/// Parse "Joined 15 March 2015", "Member since Mar 2015" and similar
+fn parse_join_date(text: &str) -> Option<NaiveDate> {
+    let date = text
+        .trim_start_matches("Joined")
+        .trim_start_matches("Member since")
+        .trim();
+
+    ["%d %B %Y", "%d %b %Y", "%B %d, %Y", "%b %d, %Y"]
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+        .or_else(|| {
+            ["%d %B %Y", "%d %b %Y"]
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(&format!("1 {}", date), format).ok())
+        })
+}
+
+fn parse_favorites(dom: &Html, client: &Client) -> Vec<FavoriteMovie> {
+    let poster_selector = Selector::parse("#favourites .film-poster").unwrap();
+    let img_selector = Selector::parse("img").unwrap();
+
+    dom.select(&poster_selector)
+        .filter_map(|poster| {
+            let slug = poster.value().attr("data-film-slug")?;
+            let name = poster.value().attr("data-film-name")
+                .or_else(|| poster.select(&img_selector).next().and_then(|img| img.value().attr("alt")))
+                .unwrap_or(slug);
+
+            Some(FavoriteMovie {
+                name: name.to_string(),
+                slug: slug.to_string(),
+                url: client.url(&format!("film/{}/", slug)),
+                film_id: poster.value().attr("data-film-id").and_then(|id| id.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+fn parse_pinned_reviews(dom: &Html, client: &Client) -> Vec<Review> {
+    let review_selector = Selector::parse("#pinned-reviews .film-detail").unwrap();
+    dom.select(&review_selector)
+        .filter_map(|review| parse_review(review, client))
+        .collect()
+}
+
+fn parse_recent_activity(dom: &Html) -> Vec<RecentFilm> {
+    let item_selector = Selector::parse("#recent-activity .poster-container").unwrap();
+    let poster_selector = Selector::parse(".film-poster[data-film-slug]").unwrap();
+    let rating_selector = Selector::parse(".rating").unwrap();
+    let liked_selector = Selector::parse(".icon-liked").unwrap();
+    let review_selector = Selector::parse(".icon-review").unwrap();
+
+    dom.select(&item_selector)
+        .filter_map(|item| {
+            let poster = item.select(&poster_selector).next()?;
+            let slug = poster.value().attr("data-film-slug")?;
+
+            Some(RecentFilm {
+                name: poster.value().attr("data-film-name").unwrap_or(slug).to_string(),
+                slug: slug.to_string(),
+                rating: item.select(&rating_selector).next()
+                    .and_then(|el| el.value().attr("class"))
+                    .and_then(parse_rating_class),
+                liked: item.select(&liked_selector).next().is_some(),
+                reviewed: item.select(&review_selector).next().is_some(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_join_date() {
+        assert_eq!(parse_join_date("Joined 15 March 2015"), NaiveDate::from_ymd_opt(2015, 3, 15));
+        assert_eq!(parse_join_date("Member since Mar 2015"), NaiveDate::from_ymd_opt(2015, 3, 1));
+        assert_eq!(parse_join_date("a while ago"), None);
+    }
 
-    pub async fn get_profile(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse user profile from the page
-        Ok(HashMap::new())
+    #[test]
+    fn test_social_service() {
+        assert_eq!(social_service("https://x.com/someone").as_deref(), Some("twitter"));
+        assert_eq!(social_service("https://www.instagram.com/someone/").as_deref(), Some("instagram"));
+        assert_eq!(social_service("not a url"), None);
     }
 }
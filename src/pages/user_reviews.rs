@@ -1,24 +1,33 @@
-use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use futures::TryStreamExt;
+use crate::core::{Client, Paginator, Result};
+use crate::models::review::{review_listing, Review};
+use crate::utils::transform::build_user_section_url;
 
 #[derive(Debug)]
 pub struct UserReviews {
     username: String,
+    client: Client,
 }
 
 impl UserReviews {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
-    pub async fn get_reviews(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/films/reviews/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse user reviews from the page
-        Ok(HashMap::new())
+    /// Every review written, most recent first
+    pub async fn get_reviews(&self) -> Result<Vec<Review>> {
+        self.reviews().try_collect().await
+    }
+
+    /// Stream of the reviews written, page by page
+    pub fn reviews(&self) -> Paginator<Review> {
+        review_listing(&self.client, &build_user_section_url(self.client.base_url(), &self.username, "films/reviews"))
     }
 }
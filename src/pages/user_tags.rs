@@ -1,22 +1,28 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use crate::core::{Client, Result};
+use crate::utils::transform::build_user_section_url;
 
 #[derive(Debug)]
 pub struct UserTags {
     username: String,
+    client: Client,
 }
 
 impl UserTags {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
     pub async fn get_tags(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/tags/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
+        let url = build_user_section_url(self.client.base_url(), &self.username, "tags");
+        let _dom = self.client.get_page(&url).await?;
         
         // TODO: Parse user tags from the page
         Ok(HashMap::new())
@@ -1,60 +1,57 @@
 use std::collections::HashMap;
-use crate::core::{Client, Result, constants::DOMAIN};
+use futures::TryStreamExt;
+use crate::core::{Client, Paginator, Result};
+use crate::pages::{collect_by_key, parse_poster_films};
+use crate::utils::transform::build_user_section_url;
 use crate::models::WatchlistMovie;
-use scraper::Selector;
 
 #[derive(Debug)]
 pub struct UserWatchlist {
     username: String,
+    client: Client,
 }
 
 impl UserWatchlist {
     pub fn new(username: &str) -> Self {
+        Self::new_with_client(&Client::new(), username)
+    }
+
+    pub fn new_with_client(client: &Client, username: &str) -> Self {
         Self {
             username: username.to_string(),
+            client: client.clone(),
         }
     }
 
+    /// Every film in the watchlist, keyed by slug, with `name`, `slug`, `url`, `film_id`, `rating` and `liked`
     pub async fn get_watchlist(&self) -> Result<HashMap<String, serde_json::Value>> {
-        let client = Client::new();
-        let url = format!("{}/{}/watchlist/", DOMAIN, self.username);
-        let _dom = client.get_page(&url).await?;
-        
-        // TODO: Parse user watchlist from the page
-        Ok(HashMap::new())
+        collect_by_key(self.poster_films(), "slug").await
     }
-    
+
+    /// Every film in the watchlist, keyed by slug
     pub async fn get_watchlist_movies(&self) -> Result<HashMap<String, WatchlistMovie>> {
-        let client = Client::new();
-        let url = format!("{}/{}/watchlist/", DOMAIN, self.username);
-        let dom = client.get_page(&url).await?;
-        
-        let mut movies = HashMap::new();
-        
-        // Parse watchlist movies from the HTML
-        let poster_selector = Selector::parse("li.poster-container").unwrap();
-        let film_selector = Selector::parse("div.film-poster").unwrap();
-        
-        for poster in dom.select(&poster_selector) {
-            if let Some(film) = poster.select(&film_selector).next() {
-                if let Some(slug_attr) = film.value().attr("data-film-slug") {
-                    let slug = slug_attr.to_string();
-                    if let Some(title_attr) = film.value().attr("data-film-name") {
-                        let name = title_attr.to_string();
-                        let movie_url = format!("{}/film/{}/", DOMAIN, slug);
-                        
-                        let movie = WatchlistMovie {
-                            name: name.clone(),
-                            slug: slug.clone(),
-                            url: movie_url,
-                        };
-                        
-                        movies.insert(slug, movie);
-                    }
-                }
-            }
-        }
-        
-        Ok(movies)
+        self.movies()
+            .map_ok(|movie| (movie.slug.clone(), movie))
+            .try_collect()
+            .await
+    }
+
+    /// Stream of the films in the watchlist
+    pub fn movies(&self) -> Paginator<WatchlistMovie> {
+        let url = build_user_section_url(self.client.base_url(), &self.username, "watchlist");
+        let base_url = self.client.base_url().to_string();
+
+        Paginator::for_url(&self.client, &url, move |dom, _| {
+            Ok(parse_poster_films(dom, &base_url)
+                .into_iter()
+                .filter_map(|film| serde_json::from_value(film).ok())
+                .collect())
+        })
+    }
+
+    fn poster_films(&self) -> Paginator<serde_json::Value> {
+        let url = build_user_section_url(self.client.base_url(), &self.username, "watchlist");
+        let base_url = self.client.base_url().to_string();
+        Paginator::for_url(&self.client, &url, move |dom, _| Ok(parse_poster_films(dom, &base_url)))
     }
 }
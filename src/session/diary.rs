@@ -0,0 +1,220 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use crate::core::{Error, Result};
+use crate::session::{Session, bool_field, validate_slug};
+use crate::utils::validators::is_valid_rating;
+
+/// A diary entry to log with [`Session::log_film`].
+///
+/// ```no_run
+/// use rustboxd::{chrono::NaiveDate, session::LogEntry};
+///
+/// let entry = LogEntry::new("the-matrix", NaiveDate::from_ymd_opt(2024, 3, 15).unwrap())
+///     .rating(4.5)
+///     .liked(true)
+///     .review("Still holds up.")
+///     .tags(&["rewatch-club", "cinema"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub slug: String,
+    pub date: NaiveDate,
+    pub rating: Option<f32>,
+    pub liked: bool,
+    pub rewatch: bool,
+    pub review: Option<String>,
+    pub contains_spoilers: bool,
+    pub tags: Vec<String>,
+}
+
+impl LogEntry {
+    pub fn new(slug: &str, date: NaiveDate) -> Self {
+        Self {
+            slug: slug.to_string(),
+            date,
+            rating: None,
+            liked: false,
+            rewatch: false,
+            review: None,
+            contains_spoilers: false,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Star rating, 0.5 to 5.0 in half-star steps
+    pub fn rating(mut self, rating: f32) -> Self {
+        self.rating = Some(rating);
+        self
+    }
+
+    pub fn liked(mut self, liked: bool) -> Self {
+        self.liked = liked;
+        self
+    }
+
+    pub fn rewatch(mut self, rewatch: bool) -> Self {
+        self.rewatch = rewatch;
+        self
+    }
+
+    pub fn review(mut self, review: &str) -> Self {
+        self.review = Some(review.to_string());
+        self
+    }
+
+    pub fn contains_spoilers(mut self, contains_spoilers: bool) -> Self {
+        self.contains_spoilers = contains_spoilers;
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_slug(&self.slug)?;
+        if let Some(rating) = self.rating {
+            validate_rating(rating)?;
+        }
+        if self.tags.iter().any(|t| t.trim().is_empty() || t.contains(',')) {
+            return Err(Error::InvalidInput("Tags must be non-empty and cannot contain commas".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// A diary entry accepted by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiaryEntryConfirmation {
+    pub slug: String,
+    pub film_id: u64,
+    /// Id of the new entry, used by [`Session::delete_diary_entry`]
+    pub viewing_id: Option<u64>,
+    pub date: NaiveDate,
+    pub rating: Option<f32>,
+    pub liked: bool,
+    pub rewatch: bool,
+}
+
+/// A rating accepted by the server; `None` means the rating was removed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingConfirmation {
+    pub slug: String,
+    pub film_id: u64,
+    pub rating: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LikeConfirmation {
+    pub slug: String,
+    pub film_id: u64,
+    pub liked: bool,
+}
+
+impl Session {
+    /// Log a film in the diary, with the same form the website's "Log" dialog submits
+    pub async fn log_film(&self, entry: &LogEntry) -> Result<DiaryEntryConfirmation> {
+        entry.validate()?;
+        let film_id = self.film_id(&entry.slug).await?;
+
+        let film_id_field = film_id.to_string();
+        let date = entry.date.format("%Y-%m-%d").to_string();
+        let rating = form_rating(entry.rating);
+        let tags = entry.tags.join(",");
+        let result = self.submit_form("s/save-diary-entry", &[
+            ("json", "true"),
+            ("filmId", &film_id_field),
+            ("specifiedDate", "true"),
+            ("viewingDateStr", &date),
+            ("rating", &rating),
+            ("liked", bool_field(entry.liked)),
+            ("rewatch", bool_field(entry.rewatch)),
+            ("review", entry.review.as_deref().unwrap_or("")),
+            ("containsSpoilers", bool_field(entry.contains_spoilers)),
+            ("tags", &tags),
+        ]).await?;
+
+        Ok(DiaryEntryConfirmation {
+            slug: entry.slug.clone(),
+            film_id,
+            viewing_id: result["viewingId"].as_u64(),
+            date: entry.date,
+            rating: entry.rating,
+            liked: entry.liked,
+            rewatch: entry.rewatch,
+        })
+    }
+
+    /// Rate a film without logging it; `None` removes the rating
+    pub async fn rate_film(&self, slug: &str, rating: Option<f32>) -> Result<RatingConfirmation> {
+        validate_slug(slug)?;
+        if let Some(rating) = rating {
+            validate_rating(rating)?;
+        }
+        let film_id = self.film_id(slug).await?;
+
+        self.submit_form(&format!("s/film:{}/rate/", film_id), &[("rating", &form_rating(rating))]).await?;
+
+        Ok(RatingConfirmation {
+            slug: slug.to_string(),
+            film_id,
+            rating,
+        })
+    }
+
+    /// Like or unlike a film
+    pub async fn like_film(&self, slug: &str, liked: bool) -> Result<LikeConfirmation> {
+        validate_slug(slug)?;
+        let film_id = self.film_id(slug).await?;
+
+        self.submit_form(&format!("s/film:{}/like/", film_id), &[("liked", bool_field(liked))]).await?;
+
+        Ok(LikeConfirmation {
+            slug: slug.to_string(),
+            film_id,
+            liked,
+        })
+    }
+
+    /// Delete a diary entry by the `viewing_id` returned when it was logged
+    pub async fn delete_diary_entry(&self, viewing_id: u64) -> Result<()> {
+        self.submit_form(&format!("s/viewing:{}/delete/", viewing_id), &[]).await?;
+        Ok(())
+    }
+}
+
+fn validate_rating(rating: f32) -> Result<()> {
+    if is_valid_rating(rating) {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput(format!("Invalid rating {}: must be 0.5 to 5.0 in steps of 0.5", rating)))
+    }
+}
+
+/// Ratings are sent in half stars, with 0 meaning no rating
+fn form_rating(rating: Option<f32>) -> String {
+    rating.map(|r| (r * 2.0).round() as u8).unwrap_or(0).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_rating() {
+        assert_eq!(form_rating(Some(4.5)), "9");
+        assert_eq!(form_rating(Some(0.5)), "1");
+        assert_eq!(form_rating(None), "0");
+    }
+
+    #[test]
+    fn test_log_entry_validation() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+        assert!(LogEntry::new("the-matrix", date).rating(4.5).validate().is_ok());
+        assert!(matches!(LogEntry::new("the-matrix", date).rating(4.3).validate(), Err(Error::InvalidInput(_))));
+        assert!(matches!(LogEntry::new("The Matrix", date).validate(), Err(Error::InvalidInput(_))));
+        assert!(matches!(LogEntry::new("the-matrix", date).tags(&["a,b"]).validate(), Err(Error::InvalidInput(_))));
+    }
+}
@@ -0,0 +1,264 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use crate::core::{Error, Result};
+use crate::models::List;
+use crate::utils::normalize_letterboxd_url;
+use crate::session::{Session, bool_field, validate_slug};
+
+/// Contents of a list to create or save with a [`Session`].
+///
+/// Saving a draft replaces the whole list: its details, its films and
+/// their order.
+///
+/// ```no_run
+/// use rustboxd::session::ListDraft;
+///
+/// let draft = ListDraft::new("Favourite Heists")
+///     .description("Crews, plans and things going wrong.")
+///     .ranked(true)
+///     .tags(&["heist", "crime"])
+///     .film("heat-1995")
+///     .film_with_notes("rififi", "The silent break-in.");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListDraft {
+    pub title: String,
+    pub description: Option<String>,
+    pub ranked: bool,
+    pub public: bool,
+    pub tags: Vec<String>,
+    pub entries: Vec<ListDraftEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListDraftEntry {
+    pub slug: String,
+    /// Known film id, saving a lookup of the film page
+    pub film_id: Option<u64>,
+    pub notes: Option<String>,
+}
+
+/// A list saved by the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListConfirmation {
+    pub list_id: u64,
+    /// URL of the list, when the server returned one
+    pub url: Option<String>,
+    pub film_count: usize,
+}
+
+impl ListDraft {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            description: None,
+            ranked: false,
+            public: true,
+            tags: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// A draft with the same details and films as a scraped list, e.g. to copy it to another account.
+    ///
+    /// Fails if the list has fewer films than it says it holds, as saving
+    /// the draft would then drop the missing ones.
+    pub fn from_list(list: &List) -> Result<Self> {
+        if list.films.len() != list.film_count as usize {
+            return Err(Error::InvalidInput(format!(
+                "Only {} of the {} films of {} were read", list.films.len(), list.film_count, list.url
+            )));
+        }
+
+        let mut films: Vec<_> = list.films.iter().collect();
+        films.sort_by_key(|film| film.position);
+
+        Ok(Self {
+            title: list.title.clone(),
+            description: list.description.clone(),
+            ranked: list.is_ranked,
+            public: list.is_public,
+            tags: list.tags.clone(),
+            entries: films
+                .into_iter()
+                .map(|film| ListDraftEntry {
+                    slug: film.slug.clone(),
+                    film_id: film.film_id,
+                    notes: film.notes.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Show the films numbered, in order
+    pub fn ranked(mut self, ranked: bool) -> Self {
+        self.ranked = ranked;
+        self
+    }
+
+    pub fn public(mut self, public: bool) -> Self {
+        self.public = public;
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> Self {
+        self.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Append a film
+    pub fn film(self, slug: &str) -> Self {
+        self.entry(slug, None)
+    }
+
+    /// Append a film with notes shown next to it
+    pub fn film_with_notes(self, slug: &str, notes: &str) -> Self {
+        self.entry(slug, Some(notes))
+    }
+
+    fn entry(mut self, slug: &str, notes: Option<&str>) -> Self {
+        self.entries.push(ListDraftEntry {
+            slug: slug.to_string(),
+            film_id: None,
+            notes: notes.map(str::to_string),
+        });
+        self
+    }
+
+    /// Put the films in the order of `slugs`, which must name every film exactly once
+    pub fn reorder(mut self, slugs: &[&str]) -> Result<Self> {
+        if slugs.len() != self.entries.len() {
+            return Err(Error::InvalidInput(format!(
+                "Expected {} films to reorder, got {}", self.entries.len(), slugs.len()
+            )));
+        }
+
+        let mut entries = Vec::with_capacity(slugs.len());
+        for slug in slugs {
+            let index = self.entries
+                .iter()
+                .position(|entry| entry.slug == *slug)
+                .ok_or_else(|| Error::InvalidInput(format!("{} is not in the list", slug)))?;
+            entries.push(self.entries.remove(index));
+        }
+        self.entries = entries;
+        Ok(self)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.title.trim().is_empty() {
+            return Err(Error::InvalidInput("List title cannot be empty".to_string()));
+        }
+        for entry in &self.entries {
+            validate_slug(&entry.slug)?;
+        }
+        if self.tags.iter().any(|t| t.trim().is_empty() || t.contains(',')) {
+            return Err(Error::InvalidInput("Tags must be non-empty and cannot contain commas".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl Session {
+    pub async fn add_to_watchlist(&self, slug: &str) -> Result<()> {
+        validate_slug(slug)?;
+        self.submit_form(&format!("film/{}/add-to-watchlist/", slug), &[]).await?;
+        Ok(())
+    }
+
+    pub async fn remove_from_watchlist(&self, slug: &str) -> Result<()> {
+        validate_slug(slug)?;
+        self.submit_form(&format!("film/{}/remove-from-watchlist/", slug), &[]).await?;
+        Ok(())
+    }
+
+    /// Create a new list in the signed-in account
+    pub async fn create_list(&self, draft: &ListDraft) -> Result<ListConfirmation> {
+        self.save_list(None, draft).await
+    }
+
+    /// Replace the details and films of an existing list
+    pub async fn edit_list(&self, list_id: u64, draft: &ListDraft) -> Result<ListConfirmation> {
+        self.save_list(Some(list_id), draft).await
+    }
+
+    /// Reorder the films of a list scraped from the signed-in account
+    pub async fn reorder_list(&self, list: &List, slugs: &[&str]) -> Result<ListConfirmation> {
+        let list_id = list.list_id
+            .ok_or_else(|| Error::InvalidInput(format!("No list id for {}", list.url)))?;
+        self.edit_list(list_id, &ListDraft::from_list(list)?.reorder(slugs)?).await
+    }
+
+    pub async fn delete_list(&self, list_id: u64) -> Result<()> {
+        self.submit_form(&format!("s/film-list:{}/delete/", list_id), &[]).await?;
+        Ok(())
+    }
+
+    /// Copy a list, typically scraped from another account, into the signed-in account
+    pub async fn clone_list(&self, list: &List) -> Result<ListConfirmation> {
+        self.create_list(&ListDraft::from_list(list)?).await
+    }
+
+    async fn save_list(&self, list_id: Option<u64>, draft: &ListDraft) -> Result<ListConfirmation> {
+        draft.validate()?;
+
+        let mut entries = Vec::with_capacity(draft.entries.len());
+        for (index, entry) in draft.entries.iter().enumerate() {
+            let film_id = match entry.film_id {
+                Some(id) => id,
+                None => self.film_id(&entry.slug).await?,
+            };
+            entries.push(json!({
+                "filmId": film_id,
+                "position": index + 1,
+                "review": entry.notes.as_deref().unwrap_or(""),
+                "containsSpoilers": false,
+            }));
+        }
+
+        let list_id_field = list_id.map(|id| id.to_string()).unwrap_or_default();
+        let tags = draft.tags.join(",");
+        let entries_field = serde_json::Value::Array(entries).to_string();
+        let result = self.submit_form("s/save-list", &[
+            ("filmListId", &list_id_field),
+            ("name", &draft.title),
+            ("notes", draft.description.as_deref().unwrap_or("")),
+            ("tags", &tags),
+            ("numberedList", bool_field(draft.ranked)),
+            ("publicList", bool_field(draft.public)),
+            ("entries", &entries_field),
+        ]).await?;
+
+        let list_id = result["filmListId"].as_u64()
+            .or(list_id)
+            .ok_or_else(|| Error::FormRejected("No list id in the response".to_string()))?;
+
+        Ok(ListConfirmation {
+            list_id,
+            url: result["url"].as_str().map(|url| normalize_letterboxd_url(self.client.base_url(), url)),
+            film_count: draft.entries.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorder() {
+        let draft = ListDraft::new("Heists").film("heat-1995").film("rififi").film("thief");
+
+        let reordered = draft.clone().reorder(&["thief", "heat-1995", "rififi"]).unwrap();
+        let slugs: Vec<_> = reordered.entries.iter().map(|e| e.slug.as_str()).collect();
+        assert_eq!(slugs, ["thief", "heat-1995", "rififi"]);
+
+        assert!(draft.clone().reorder(&["thief", "heat-1995"]).is_err());
+        assert!(draft.reorder(&["thief", "heat-1995", "alien"]).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+//! Signed-in access to Letterboxd.
+//!
+//! A [`Session`] is created with [`Client::login`]. It keeps the session
+//! cookie in the client's cookie jar and the CSRF token needed by every form
+//! the website submits, and signs in again when the session expires.
+
+mod diary;
+mod lists;
+mod network;
+
+pub use diary::{DiaryEntryConfirmation, LikeConfirmation, LogEntry, RatingConfirmation};
+pub use lists::{ListConfirmation, ListDraft, ListDraftEntry};
+pub use network::MirrorReport;
+
+use std::sync::{Arc, Mutex};
+use scraper::{Html, Selector};
+use crate::core::{Client, Error, HttpRequest, HttpResponse, Result};
+use crate::models::Movie;
+use crate::utils::validators::is_valid_film_slug;
+use crate::core::constants::{CSRF_COOKIE, CSRF_FIELD, LOGIN_PATH, LOGOUT_PATH, SIGN_IN_PATH};
+
+/// An authenticated Letterboxd session.
+///
+/// Clones share the same sign-in state.
+///
+/// ```no_run
+/// use rustboxd::{Client, pages::UserDiary};
+///
+/// # async fn run() -> rustboxd::Result<()> {
+/// let session = Client::new().login("username", "password").await?;
+///
+/// // Every model works signed in through the session's client
+/// let diary = UserDiary::new_with_client(session.client(), "username");
+///
+/// // Pages fetched through the session sign in again if the session expired
+/// let page = session.get_page("https://letterboxd.com/activity/").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Session {
+    client: Client,
+    username: String,
+    password: String,
+    csrf: Arc<Mutex<String>>,
+}
+
+impl Client {
+    /// Sign in with the website's login form and return the session
+    pub async fn login(&self, username: &str, password: &str) -> Result<Session> {
+        let session = Session {
+            client: self.clone(),
+            username: username.to_string(),
+            password: password.to_string(),
+            csrf: Arc::new(Mutex::new(String::new())),
+        };
+        session.sign_in().await?;
+        Ok(session)
+    }
+}
+
+impl Session {
+    /// The signed-in client; cookies are shared with every clone of it
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// CSRF token sent with every form submission
+    pub fn csrf_token(&self) -> String {
+        self.csrf.lock().unwrap().clone()
+    }
+
+    /// Sign in again, replacing the session cookie and CSRF token
+    pub async fn sign_in(&self) -> Result<()> {
+        let sign_in_url = self.client.url(SIGN_IN_PATH);
+        let page = self.client.send(HttpRequest::get(&sign_in_url)).await?;
+        self.client.check_response_errors(&sign_in_url, &page)?;
+
+        let csrf = extract_csrf(&page.body)
+            .or_else(|| self.client.cookie_jar().get(&sign_in_url, CSRF_COOKIE))
+            .ok_or_else(|| Error::LoginFailed("No CSRF token on the sign-in page".to_string()))?;
+
+        let login_url = self.client.url(LOGIN_PATH);
+        let response = self.client.send(HttpRequest::post_form(&login_url, &[
+            (CSRF_FIELD, &csrf),
+            ("username", &self.username),
+            ("password", &self.password),
+            ("remember", "true"),
+            ("authenticationCode", ""),
+        ])).await?;
+        self.client.check_response_errors(&login_url, &response)?;
+
+        let result: serde_json::Value = serde_json::from_str(&response.body)
+            .map_err(|_| Error::LoginFailed("Unexpected response to the login form".to_string()))?;
+        if result["result"] != "success" {
+            return Err(Error::LoginFailed(form_messages(&result)
+                .unwrap_or_else(|| "Invalid username or password".to_string())));
+        }
+
+        // The token may be rotated on sign-in
+        let csrf = result["csrf"].as_str().map(str::to_string)
+            .or_else(|| self.client.cookie_jar().get(&login_url, CSRF_COOKIE))
+            .unwrap_or(csrf);
+        *self.csrf.lock().unwrap() = csrf;
+        Ok(())
+    }
+
+    /// Sign out, ending the session on the server
+    pub async fn logout(&self) -> Result<()> {
+        let url = self.client.url(LOGOUT_PATH);
+        let response = self.client.send(HttpRequest::post_form(&url, &[(CSRF_FIELD, &self.csrf_token())])).await?;
+        self.client.check_response_errors(&url, &response)
+    }
+
+    /// Fetch and parse a page as the signed-in user
+    pub async fn get_page(&self, url: &str) -> Result<Html> {
+        let response = self.send(|| HttpRequest::get(url)).await?;
+        Ok(Html::parse_document(&response.body))
+    }
+
+    /// Submit a form as the website would, with the CSRF token added
+    pub async fn post_form(&self, url: &str, fields: &[(&str, &str)]) -> Result<HttpResponse> {
+        self.send(|| {
+            let csrf = self.csrf_token();
+            let mut fields = fields.to_vec();
+            fields.push((CSRF_FIELD, &csrf));
+            HttpRequest::post_form(url, &fields)
+        }).await
+    }
+
+    /// Submit a form that answers with JSON, failing with [`Error::FormRejected`] if it was refused
+    pub async fn submit_form(&self, path: &str, fields: &[(&str, &str)]) -> Result<serde_json::Value> {
+        let response = self.post_form(&self.client.url(path), fields).await?;
+
+        let result: serde_json::Value = serde_json::from_str(&response.body)
+            .map_err(|_| Error::FormRejected(format!("Unexpected response from {}", response.url)))?;
+        match &result["result"] {
+            serde_json::Value::Bool(true) => Ok(result),
+            serde_json::Value::String(s) if s == "success" => Ok(result),
+            _ => Err(Error::FormRejected(form_messages(&result)
+                .unwrap_or_else(|| format!("{} was not accepted", path)))),
+        }
+    }
+
+    /// Letterboxd's numeric id for a film, needed by most forms
+    pub async fn film_id(&self, slug: &str) -> Result<u64> {
+        Movie::new_with_client(&self.client, slug)
+            .await?
+            .movie_id
+            .ok_or_else(|| Error::parse(&self.client.url(&format!("film/{}/", slug)), "[data-film-id]", "film id"))
+    }
+
+    /// Send a request, signing in again and resending it once if the session has expired.
+    ///
+    /// A request that is still refused after signing in again fails with its
+    /// own status error, or [`Error::SessionExpired`] if it was sent back to
+    /// the sign-in page.
+    async fn send(&self, request: impl Fn() -> HttpRequest) -> Result<HttpResponse> {
+        let mut response = self.client.send(request()).await?;
+
+        if self.is_signed_out(&response) {
+            self.sign_in().await?;
+            response = self.client.send(request()).await?;
+            if self.redirected_to_sign_in(&response) {
+                return Err(Error::SessionExpired);
+            }
+        }
+
+        self.client.check_response_errors(&response.url, &response)?;
+        Ok(response)
+    }
+
+    /// Whether the server treated the request as coming from a signed-out visitor.
+    ///
+    /// A `403` is not enough: private and blocked members answer it whatever the session.
+    fn is_signed_out(&self, response: &HttpResponse) -> bool {
+        response.status == 401 || self.redirected_to_sign_in(response)
+    }
+
+    fn redirected_to_sign_in(&self, response: &HttpResponse) -> bool {
+        response.url.starts_with(&self.client.url(SIGN_IN_PATH))
+    }
+}
+
+impl std::fmt::Debug for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Session")
+            .field("client", &self.client)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+fn validate_slug(slug: &str) -> Result<()> {
+    if is_valid_film_slug(slug) {
+        Ok(())
+    } else {
+        Err(Error::InvalidInput(format!("Invalid film slug: {}", slug)))
+    }
+}
+
+/// Booleans as the website's forms send them
+fn bool_field(value: bool) -> &'static str {
+    if value { "true" } else { "false" }
+}
+
+fn extract_csrf(html: &str) -> Option<String> {
+    let selector = Selector::parse(&format!("input[name=\"{}\"]", CSRF_FIELD)).unwrap();
+    Html::parse_document(html)
+        .select(&selector)
+        .next()
+        .and_then(|input| input.value().attr("value"))
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Error messages of a JSON form response, joined into one line
+fn form_messages(result: &serde_json::Value) -> Option<String> {
+    let messages: Vec<&str> = result["messages"]
+        .as_array()?
+        .iter()
+        .filter_map(|m| m.as_str())
+        .collect();
+
+    (!messages.is_empty()).then(|| messages.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_out_detection() {
+        let session = Session {
+            client: Client::builder().base_url("https://mirror.test/letterboxd").build().unwrap(),
+            username: "alice".to_string(),
+            password: "secret".to_string(),
+            csrf: Arc::new(Mutex::new(String::new())),
+        };
+
+        assert!(session.is_signed_out(&HttpResponse::new("https://mirror.test/letterboxd/activity/", 401, "")));
+        assert!(session.is_signed_out(&HttpResponse::new("https://mirror.test/letterboxd/sign-in/?next=/activity/", 200, "")));
+        assert!(!session.is_signed_out(&HttpResponse::new("https://mirror.test/letterboxd/private-member/", 403, "")));
+        assert!(!session.is_signed_out(&HttpResponse::new("https://mirror.test/letterboxd/activity/", 200, "")));
+        assert!(!session.is_signed_out(&HttpResponse::new("https://mirror.test/letterboxd/film/sign-in-blood/", 200, "")));
+    }
+
+    #[test]
+    fn test_extract_csrf() {
+        let html = r#"<form><input type="hidden" name="__csrf" value="abc123"></form>"#;
+
+        assert_eq!(extract_csrf(html).as_deref(), Some("abc123"));
+        assert_eq!(extract_csrf("<form></form>"), None);
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use crate::core::{Error, Result};
+use crate::pages::UserNetwork;
+use crate::session::Session;
+use crate::utils::validators::is_valid_username;
+
+/// Attempts made for one follow in [`Session::mirror_following`] when the server rate limits us
+const MIRROR_ATTEMPTS: u32 = 3;
+
+/// Longest `Retry-After` that [`Session::mirror_following`] waits out before giving up on a follow
+const MIRROR_MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// Outcome of [`Session::mirror_following`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MirrorReport {
+    /// Members followed by this run
+    pub followed: Vec<String>,
+    /// Members the signed-in user already followed
+    pub already_following: Vec<String>,
+    /// Members that could not be followed, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+impl Session {
+    pub async fn follow(&self, username: &str) -> Result<()> {
+        self.member_action(username, "follow").await
+    }
+
+    pub async fn unfollow(&self, username: &str) -> Result<()> {
+        self.member_action(username, "unfollow").await
+    }
+
+    pub async fn block(&self, username: &str) -> Result<()> {
+        self.member_action(username, "block").await
+    }
+
+    /// Follow everyone `username` follows that the signed-in user does not follow yet.
+    ///
+    /// Follows are sent one at a time, `delay` apart, on top of the client's
+    /// own rate limit. When the server rate limits a follow, it is retried
+    /// after the `Retry-After` delay (or `delay`), unless the server asks
+    /// for more than a minute. Other failures are
+    /// recorded in the report and the run continues, except for an expired
+    /// session, which stops it.
+    pub async fn mirror_following(&self, username: &str, delay: Duration) -> Result<MirrorReport> {
+        let theirs = UserNetwork::new_with_client(&self.client, username).get_following().await?;
+        // Usernames are case-insensitive, but profile links keep whatever casing they were typed with
+        let ours: HashSet<String> = UserNetwork::new_with_client(&self.client, &self.username)
+            .get_following()
+            .await?
+            .into_keys()
+            .map(|member| member.to_lowercase())
+            .collect();
+
+        let mut candidates: Vec<&String> = theirs.keys()
+            .filter(|member| !member.eq_ignore_ascii_case(&self.username))
+            .collect();
+        candidates.sort();
+
+        let mut report = MirrorReport::default();
+        for member in candidates {
+            if ours.contains(&member.to_lowercase()) {
+                report.already_following.push(member.clone());
+                continue;
+            }
+
+            if !report.followed.is_empty() || !report.failed.is_empty() {
+                tokio::time::sleep(delay).await;
+            }
+
+            match self.follow_paced(member, delay).await {
+                Ok(()) => report.followed.push(member.clone()),
+                Err(e @ (Error::SessionExpired | Error::LoginFailed(_))) => return Err(e),
+                Err(e) => report.failed.push((member.clone(), e.to_string())),
+            }
+        }
+
+        Ok(report)
+    }
+
+    async fn follow_paced(&self, username: &str, delay: Duration) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            match self.follow(username).await {
+                Err(e) if e.is_rate_limited() && attempt < MIRROR_ATTEMPTS => {
+                    let wait = e.retry_after().unwrap_or(delay);
+                    if wait > MIRROR_MAX_WAIT {
+                        return Err(e);
+                    }
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn member_action(&self, username: &str, action: &str) -> Result<()> {
+        if !is_valid_username(username) {
+            return Err(Error::InvalidInput(format!("Invalid username: {}", username)));
+        }
+        if username.eq_ignore_ascii_case(&self.username) {
+            return Err(Error::InvalidInput(format!("Cannot {} yourself", action)));
+        }
+
+        self.submit_form(&format!("{}/{}/", username, action), &[]).await?;
+        Ok(())
+    }
+}
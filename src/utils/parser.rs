@@ -8,21 +8,20 @@ pub fn try_parse<T: std::str::FromStr>(value: &str) -> Option<T> {
 
 /// Extract and convert shorthand notation (e.g., '6.3K') to an integer
 pub fn extract_and_convert_shorthand(text: &str) -> u32 {
+    parse_shorthand(text).unwrap_or(0)
+}
+
+/// Convert shorthand notation (e.g., '6.3K') to an integer, or `None` if it is not a number
+pub fn parse_shorthand(text: &str) -> Option<u32> {
     let cleaned = text.trim().replace(',', "");
     
     if cleaned.contains('K') {
-        if let Ok(num) = cleaned.replace('K', "").parse::<f32>() {
-            return (num * 1000.0) as u32;
-        }
+        cleaned.replace('K', "").parse::<f32>().ok().map(|num| (num * 1000.0) as u32)
     } else if cleaned.contains('M') {
-        if let Ok(num) = cleaned.replace('M', "").parse::<f32>() {
-            return (num * 1_000_000.0) as u32;
-        }
-    } else if let Ok(num) = cleaned.parse::<u32>() {
-        return num;
+        cleaned.replace('M', "").parse::<f32>().ok().map(|num| (num * 1_000_000.0) as u32)
+    } else {
+        cleaned.parse::<u32>().ok()
     }
-    
-    0
 }
 
 /// Extract numeric characters from a string and return them as an integer
@@ -31,40 +30,38 @@ pub fn extract_numeric_text(text: &str) -> Option<u32> {
     numeric_only.parse().ok()
 }
 
-/// Parse an ISO 8601 formatted date string
+/// Parse an ISO 8601 formatted date string.
+///
+/// Malformed dates fail with [`Error::Parse`], naming the date text in place of a URL.
 pub fn parse_iso_date(iso_date_str: &str) -> Result<(i32, u32, u32)> {
+    let invalid = |part: &str| Error::parse(iso_date_str, "ISO date", format!("invalid {}", part));
     let parts: Vec<&str> = iso_date_str.split('T').next().unwrap_or("").split('-').collect();
     
     if parts.len() != 3 {
-        return Err(Error::Parse(format!("Invalid ISO date format: {}", iso_date_str)));
+        return Err(invalid("format"));
     }
     
-    let year = parts[0].parse::<i32>()
-        .map_err(|_| Error::Parse(format!("Invalid year in date: {}", iso_date_str)))?;
-    let month = parts[1].parse::<u32>()
-        .map_err(|_| Error::Parse(format!("Invalid month in date: {}", iso_date_str)))?;
-    let day = parts[2].parse::<u32>()
-        .map_err(|_| Error::Parse(format!("Invalid day in date: {}", iso_date_str)))?;
+    let year = parts[0].parse::<i32>().map_err(|_| invalid("year"))?;
+    let month = parts[1].parse::<u32>().map_err(|_| invalid("month"))?;
+    let day = parts[2].parse::<u32>().map_err(|_| invalid("day"))?;
     
     Ok((year, month, day))
 }
 
-/// Parse a written date string (e.g., '01 Jan 2025')
+/// Parse a written date string (e.g., '01 Jan 2025').
+///
+/// Malformed dates fail with [`Error::Parse`], naming the date text in place of a URL.
 pub fn parse_written_date(written_date_str: &str) -> Result<(i32, u32, u32)> {
+    let invalid = |part: &str| Error::parse(written_date_str, "written date", format!("invalid {}", part));
     let parts: Vec<&str> = written_date_str.split_whitespace().collect();
     
     if parts.len() != 3 {
-        return Err(Error::Parse(format!("Invalid written date format: {}", written_date_str)));
+        return Err(invalid("format"));
     }
     
-    let day = parts[0].parse::<u32>()
-        .map_err(|_| Error::Parse(format!("Invalid day in date: {}", written_date_str)))?;
-    
-    let month = month_to_index(parts[1])
-        .ok_or_else(|| Error::Parse(format!("Invalid month in date: {}", written_date_str)))?;
-    
-    let year = parts[2].parse::<i32>()
-        .map_err(|_| Error::Parse(format!("Invalid year in date: {}", written_date_str)))?;
+    let day = parts[0].parse::<u32>().map_err(|_| invalid("day"))?;
+    let month = month_to_index(parts[1]).ok_or_else(|| invalid("month"))?;
+    let year = parts[2].parse::<i32>().map_err(|_| invalid("year"))?;
     
     Ok((year, month, day))
 }
@@ -129,6 +126,16 @@ pub fn parse_rating(text: &str) -> Option<f32> {
     None
 }
 
+/// Parse a rating from the `rated-N` class of a rating element, N being half stars
+pub fn parse_rating_class(class_attr: &str) -> Option<f32> {
+    class_attr
+        .split_whitespace()
+        .find_map(|class| class.strip_prefix("rated-"))
+        .and_then(|half_stars| half_stars.parse::<u8>().ok())
+        .filter(|half_stars| (1..=10).contains(half_stars))
+        .map(|half_stars| half_stars as f32 / 2.0)
+}
+
 /// Parse runtime from text (handles formats like "142 mins", "2h 22m", "2:22")
 pub fn parse_runtime(text: &str) -> Option<u32> {
     let cleaned = text.trim().to_lowercase();
@@ -163,8 +170,7 @@ pub fn parse_runtime(text: &str) -> Option<u32> {
 /// Clean and normalize text content
 pub fn clean_text(text: &str) -> String {
     text.trim()
-        .replace('\n', " ")
-        .replace('\t', " ")
+        .replace(['\n', '\t'], " ")
         .split_whitespace()
         .collect::<Vec<&str>>()
         .join(" ")
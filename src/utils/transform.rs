@@ -1,4 +1,4 @@
-use crate::core::constants::DOMAIN;
+use regex::Regex;
 
 /// Convert regular URL to AJAX URL for pagination
 pub fn get_ajax_url(url: &str) -> String {
@@ -16,40 +16,40 @@ pub fn get_ajax_url(url: &str) -> String {
 }
 
 /// Build Letterboxd URL from components
-pub fn build_letterboxd_url(path: &str) -> String {
+pub fn build_letterboxd_url(base_url: &str, path: &str) -> String {
     let path = path.trim_start_matches('/');
-    format!("{}/{}", DOMAIN, path)
+    format!("{}/{}", base_url.trim_end_matches('/'), path)
 }
 
 /// Build user profile URL
-pub fn build_user_url(username: &str) -> String {
-    format!("{}/{}/", DOMAIN, username)
+pub fn build_user_url(base_url: &str, username: &str) -> String {
+    build_letterboxd_url(base_url, &format!("{}/", username))
 }
 
 /// Build film URL from slug
-pub fn build_film_url(slug: &str) -> String {
-    format!("{}/film/{}/", DOMAIN, slug)
+pub fn build_film_url(base_url: &str, slug: &str) -> String {
+    build_letterboxd_url(base_url, &format!("film/{}/", slug))
 }
 
 /// Build list URL from author and slug
-pub fn build_list_url(author: &str, slug: &str) -> String {
-    format!("{}/{}/list/{}/", DOMAIN, author, slug)
+pub fn build_list_url(base_url: &str, author: &str, slug: &str) -> String {
+    build_letterboxd_url(base_url, &format!("{}/list/{}/", author, slug))
 }
 
 /// Build search URL
-pub fn build_search_url(query: &str, filter: Option<&str>) -> String {
+pub fn build_search_url(base_url: &str, query: &str, filter: Option<&str>) -> String {
     let encoded_query = urlencoding::encode(query);
     
     if let Some(filter) = filter {
-        format!("{}/s/search/{}/{}/", DOMAIN, filter, encoded_query)
+        build_letterboxd_url(base_url, &format!("s/search/{}/{}/", filter, encoded_query))
     } else {
-        format!("{}/s/search/{}/", DOMAIN, encoded_query)
+        build_letterboxd_url(base_url, &format!("s/search/{}/", encoded_query))
     }
 }
 
 /// Build diary URL with optional date components
-pub fn build_diary_url(username: &str, year: Option<i32>, month: Option<u32>, day: Option<u32>) -> String {
-    let mut url = format!("{}/{}/films/diary/", DOMAIN, username);
+pub fn build_diary_url(base_url: &str, username: &str, year: Option<i32>, month: Option<u32>, day: Option<u32>) -> String {
+    let mut url = build_letterboxd_url(base_url, &format!("{}/films/diary/", username));
     
     if let Some(year) = year {
         url.push_str(&format!("for/{}/", year));
@@ -67,8 +67,8 @@ pub fn build_diary_url(username: &str, year: Option<i32>, month: Option<u32>, da
 }
 
 /// Build films URL with optional filters
-pub fn build_films_url(username: &str, filter: Option<&str>) -> String {
-    let mut url = format!("{}/{}/films/", DOMAIN, username);
+pub fn build_films_url(base_url: &str, username: &str, filter: Option<&str>) -> String {
+    let mut url = build_letterboxd_url(base_url, &format!("{}/films/", username));
     
     if let Some(filter) = filter {
         url.push_str(&format!("{}/", filter));
@@ -84,18 +84,14 @@ pub fn add_page_to_url(base_url: &str, page: u32) -> String {
 }
 
 /// Normalize Letterboxd URL (ensure it has proper format)
-pub fn normalize_letterboxd_url(url: &str) -> String {
+pub fn normalize_letterboxd_url(base_url: &str, url: &str) -> String {
     let url = url.trim();
     
     if url.starts_with("http://") || url.starts_with("https://") {
         return url.to_string();
     }
     
-    if url.starts_with("/") {
-        return format!("{}{}", DOMAIN, url);
-    }
-    
-    format!("{}/{}", DOMAIN, url)
+    build_letterboxd_url(base_url, url)
 }
 
 /// Extract pagination info from URL
@@ -122,11 +118,25 @@ pub fn remove_page_from_url(url: &str) -> String {
 }
 
 /// Build URL for specific film sections
-pub fn build_film_section_url(slug: &str, section: &str) -> String {
-    format!("{}/film/{}/{}/", DOMAIN, slug, section)
+pub fn build_film_section_url(base_url: &str, slug: &str, section: &str) -> String {
+    build_letterboxd_url(base_url, &format!("film/{}/{}/", slug, section))
 }
 
 /// Build URL for user sections
-pub fn build_user_section_url(username: &str, section: &str) -> String {
-    format!("{}/{}/{}/", DOMAIN, username, section)
+pub fn build_user_section_url(base_url: &str, username: &str, section: &str) -> String {
+    build_letterboxd_url(base_url, &format!("{}/{}/", username, section))
+}
+
+/// Point an avatar URL at another square size, e.g. 220 to 1000 pixels.
+///
+/// Handles uploaded avatars (`-0-220-0-220-crop.jpg`) and the default
+/// avatar (`avatar220.png`); other URLs are returned unchanged.
+pub fn resize_avatar_url(url: &str, size: u32) -> String {
+    let cropped = Regex::new(r"-0-\d+-0-\d+-crop").unwrap();
+    if cropped.is_match(url) {
+        return cropped.replace(url, format!("-0-{0}-0-{0}-crop", size).as_str()).into_owned();
+    }
+
+    let default = Regex::new(r"avatar\d+\.png").unwrap();
+    default.replace(url, format!("avatar{}.png", size).as_str()).into_owned()
 }
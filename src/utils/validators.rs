@@ -39,17 +39,17 @@ pub fn is_valid_search_filter(filter: &str) -> bool {
 
 /// Validate year (reasonable range for films)
 pub fn is_valid_year(year: i32) -> bool {
-    year >= 1888 && year <= 2030 // Cinema started around 1888
+    (1888..=2030).contains(&year) // Cinema started around 1888
 }
 
 /// Validate month (1-12)
 pub fn is_valid_month(month: u32) -> bool {
-    month >= 1 && month <= 12
+    (1..=12).contains(&month)
 }
 
 /// Validate day (1-31, basic validation)
 pub fn is_valid_day(day: u32) -> bool {
-    day >= 1 && day <= 31
+    (1..=31).contains(&day)
 }
 
 /// Validate Letterboxd URL format
@@ -77,8 +77,6 @@ pub fn sanitize_for_url(input: &str) -> String {
         .map(|c| {
             if c.is_alphanumeric() {
                 c
-            } else if c.is_whitespace() || c == '-' || c == '_' {
-                '-'
             } else {
                 '-'
             }
@@ -133,7 +131,7 @@ pub fn is_valid_list_slug(slug: &str) -> bool {
 
 /// Validate and normalize rating input
 pub fn normalize_rating(rating: f32) -> Option<f32> {
-    if rating < 0.0 || rating > 5.0 {
+    if !(0.0..=5.0).contains(&rating) {
         return None;
     }
     
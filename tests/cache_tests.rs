@@ -0,0 +1,158 @@
+mod common;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use rustboxd::Movie;
+use rustboxd::core::{Client, HttpCache, HttpResponse, MockTransport};
+use rustboxd::core::cache::CacheEntry;
+
+const MATRIX_URL: &str = "https://letterboxd.com/film/the-matrix/";
+
+fn cache_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rustboxd-cache-{}-{}", name, std::process::id()))
+}
+
+/// Client with only the on-disk cache, so every film lookup reaches it
+fn cached_client(transport: &Arc<MockTransport>, cache: HttpCache) -> Client {
+    Client::builder()
+        .transport(transport.clone())
+        .cache(cache)
+        .movie_cache_capacity(0)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_fresh_entries_are_served_from_disk() {
+    let dir = cache_dir("fresh");
+    let transport = Arc::new(MockTransport::new().with_page(MATRIX_URL, &common::fixture("film_the_matrix.html")));
+
+    let first = Movie::new_with_client(&cached_client(&transport, HttpCache::new(&dir)), "the-matrix").await.unwrap();
+    // A new client sharing the directory, as in a second run of a report
+    let second = Movie::new_with_client(&cached_client(&transport, HttpCache::new(&dir)), "the-matrix").await.unwrap();
+
+    assert_eq!(first.title, second.title);
+    assert_eq!(transport.request_count(MATRIX_URL), 1);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_stale_entries_are_revalidated() {
+    let dir = cache_dir("revalidate");
+    let transport = Arc::new(MockTransport::new()
+        .with_response(MATRIX_URL, HttpResponse::ok(MATRIX_URL, &common::fixture("film_the_matrix.html"))
+            .with_header("ETag", "\"v1\"")
+            .with_header("Last-Modified", "Wed, 21 Oct 2015 07:28:00 GMT"))
+        .with_response(MATRIX_URL, HttpResponse::new(MATRIX_URL, 304, "")));
+    let client = cached_client(&transport, HttpCache::new(&dir).route_ttl("/film/", Duration::ZERO));
+
+    Movie::new_with_client(&client, "the-matrix").await.unwrap();
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(movie.title, "The Matrix");
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].headers.is_empty());
+    assert!(requests[1].headers.contains(&("If-None-Match".to_string(), "\"v1\"".to_string())));
+    assert!(requests[1].headers.contains(&("If-Modified-Since".to_string(), "Wed, 21 Oct 2015 07:28:00 GMT".to_string())));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_bypass_cache_refetches_and_refreshes() {
+    let dir = cache_dir("bypass");
+    let transport = Arc::new(MockTransport::new()
+        .with_page(MATRIX_URL, "<h1 class=\"headline-1\">Old Title</h1>")
+        .with_page(MATRIX_URL, "<h1 class=\"headline-1\">New Title</h1>"));
+    let client = cached_client(&transport, HttpCache::new(&dir));
+
+    client.get_page(MATRIX_URL).await.unwrap();
+    let fresh = Movie::new_with_client(&client.bypass_cache(), "the-matrix").await.unwrap();
+    let cached = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(fresh.title, "New Title");
+    assert_eq!(cached.title, "New Title");
+    assert_eq!(transport.request_count(MATRIX_URL), 2);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_errors_and_oversized_pages_are_not_cached() {
+    let dir = cache_dir("skip");
+    let big_url = "https://letterboxd.com/film/big/";
+    let transport = Arc::new(MockTransport::new().with_page(big_url, &"x".repeat(2048)));
+    let client = cached_client(&transport, HttpCache::new(&dir).max_entry_size(1024));
+
+    client.get_page(big_url).await.unwrap();
+    client.get_page(big_url).await.unwrap();
+    assert!(client.get_page("https://letterboxd.com/film/missing/").await.is_err());
+    assert!(client.get_page("https://letterboxd.com/film/missing/").await.is_err());
+
+    assert_eq!(transport.request_count(big_url), 2);
+    assert_eq!(transport.request_count("https://letterboxd.com/film/missing/"), 2);
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_unwritable_cache_does_not_fail_fetches() {
+    // A file where the cache directory should be, so every write fails
+    let dir = cache_dir("unwritable");
+    std::fs::write(&dir, "not a directory").unwrap();
+    let transport = Arc::new(MockTransport::new().with_page(MATRIX_URL, &common::fixture("film_the_matrix.html")));
+    let client = cached_client(&transport, HttpCache::new(&dir).max_size(1024));
+
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(movie.title, "The Matrix");
+
+    std::fs::remove_file(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_concurrent_eviction_does_not_fail_fetches() {
+    let dir = cache_dir("concurrent-evict");
+    let transport = Arc::new(MockTransport::new());
+    let urls: Vec<String> = (0..16).map(|i| format!("https://letterboxd.com/film/film-{}/", i)).collect();
+    for url in &urls {
+        transport.insert_page(url, &"x".repeat(600));
+    }
+    let client = cached_client(&transport, HttpCache::new(&dir).max_size(1500));
+
+    let pages = futures::future::join_all(urls.iter().map(|url| {
+        let client = client.clone();
+        async move { client.get_page(url).await }
+    })).await;
+
+    assert!(pages.iter().all(|page| page.is_ok()));
+
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[tokio::test]
+async fn test_film_stats_are_not_served_a_week_stale() {
+    let dir = cache_dir("live-routes");
+    let stats_url = "https://letterboxd.com/csi/film/the-matrix/stats/";
+    let cache = HttpCache::new(&dir);
+    let an_hour_ago = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() - 60 * 60;
+    std::fs::create_dir_all(&dir).unwrap();
+    for (url, body) in [(MATRIX_URL, common::fixture("film_the_matrix.html")), (stats_url, "old stats".to_string())] {
+        let entry = CacheEntry { url: url.to_string(), stored_at: an_hour_ago, etag: None, last_modified: None, body };
+        std::fs::write(cache.entry_path(url), serde_json::to_vec(&entry).unwrap()).unwrap();
+    }
+    let transport = Arc::new(MockTransport::new().with_page(stats_url, "new stats"));
+    let client = cached_client(&transport, cache);
+
+    let stats = client.get_page(stats_url).await.unwrap();
+    Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(stats.root_element().text().collect::<String>(), "new stats");
+    assert_eq!(transport.request_count(stats_url), 1);
+    assert_eq!(transport.request_count(MATRIX_URL), 0);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
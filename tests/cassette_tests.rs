@@ -0,0 +1,118 @@
+mod common;
+
+use std::path::PathBuf;
+use rustboxd::{Films, List, Movie, Search, User, Error};
+use rustboxd::core::{CassetteMode, Client, MockTransport, HttpRequest, HttpResponse, Transport};
+use rustboxd::core::cassette::{RecordingTransport, ReplayTransport};
+
+/// Client replaying the cassettes shipped in `tests/fixtures/cassettes`
+fn replay_client() -> Client {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/cassettes");
+    Client::builder()
+        .cassette(CassetteMode::Replay(dir))
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_replay_movie() {
+    let movie = Movie::new_with_client(&replay_client(), "the-matrix").await.unwrap();
+
+    assert_eq!(movie.title, "The Matrix");
+    assert_eq!(movie.year, Some(1999));
+}
+
+#[tokio::test]
+async fn test_replay_recorded_not_found() {
+    let result = Movie::new_with_client(&replay_client(), "not-a-real-film").await;
+
+    assert!(matches!(result, Err(Error::MovieNotFound(_))));
+}
+
+#[tokio::test]
+async fn test_replay_user() {
+    let user = User::new_with_client(&replay_client(), "testuser").await.unwrap();
+
+    assert_eq!(user.display_name, "Test User");
+}
+
+#[tokio::test]
+async fn test_replay_list() {
+    let list = List::new_with_client(&replay_client(), "testuser", "favourite-heists").await.unwrap();
+
+    assert_eq!(list.title, "Favourite Heists");
+    assert_eq!(list.films.len(), 3);
+}
+
+#[tokio::test]
+async fn test_replay_search() {
+    let search = Search::new_with_client(&replay_client(), "blade runner", Some("films")).await.unwrap();
+
+    assert_eq!(search.results.films.len(), 2);
+}
+
+#[tokio::test]
+async fn test_replay_films() {
+    let films = Films::new_with_client(&replay_client(), "https://letterboxd.com/films/popular/", 3).await.unwrap();
+
+    assert_eq!(films.count, 3);
+    assert_eq!(films.movies["stalker"].title, "Stalker");
+}
+
+#[tokio::test]
+async fn test_replay_errors_on_unknown_url() {
+    let result = replay_client().get_page("https://letterboxd.com/film/never-recorded/").await;
+
+    assert!(matches!(result, Err(Error::RecordingMissing { ref url, .. }) if url == "https://letterboxd.com/film/never-recorded/"));
+}
+
+#[tokio::test]
+async fn test_record_then_replay() {
+    let dir = std::env::temp_dir().join(format!("rustboxd-cassette-{}", std::process::id()));
+    let url = "https://letterboxd.com/film/the-matrix/";
+
+    let transport = MockTransport::new().with_page(url, &common::fixture("film_the_matrix.html"));
+    let recorder = Client::builder()
+        .transport(transport)
+        .cassette(CassetteMode::Record(dir.clone()))
+        .build()
+        .unwrap();
+    let recorded = Movie::new_with_client(&recorder, "the-matrix").await.unwrap();
+
+    let player = Client::builder()
+        .cassette(CassetteMode::Replay(dir.clone()))
+        .build()
+        .unwrap();
+    let replayed = Movie::new_with_client(&player, "the-matrix").await.unwrap();
+
+    assert_eq!(recorded.title, replayed.title);
+    assert_eq!(recorded.genres.len(), replayed.genres.len());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_record_keeps_methods_apart_and_drops_cookies() {
+    let dir = std::env::temp_dir().join(format!("rustboxd-cassette-post-{}", std::process::id()));
+    let url = "https://letterboxd.com/user/login.do";
+
+    let transport = MockTransport::new()
+        .with_response(url, HttpResponse::new(url, 200, r#"{"result":"success"}"#).with_header("Set-Cookie", "letterboxd.user.CURRENT=secret"))
+        .with_page(url, "<html>sign in</html>");
+    let recorder = RecordingTransport::new(std::sync::Arc::new(transport), &dir);
+    recorder.execute(HttpRequest::post_form(url, &[("password", "hunter2")])).await.unwrap();
+    recorder.execute(HttpRequest::get(url)).await.unwrap();
+
+    let player = ReplayTransport::new(&dir);
+    let posted = player.execute(HttpRequest::post_form(url, &[])).await.unwrap();
+    let fetched = player.execute(HttpRequest::get(url)).await.unwrap();
+
+    assert_eq!(posted.body, r#"{"result":"success"}"#);
+    assert_eq!(posted.header("set-cookie"), None);
+    assert_eq!(fetched.body, "<html>sign in</html>");
+    for file in std::fs::read_dir(&dir).unwrap() {
+        assert!(!std::fs::read_to_string(file.unwrap().path()).unwrap().contains("secret"));
+    }
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
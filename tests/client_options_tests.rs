@@ -0,0 +1,53 @@
+mod common;
+
+use rustboxd::{Client, Error};
+use rustboxd::core::CookieJar;
+use common::LocalServer;
+
+#[tokio::test]
+async fn test_cookie_jar_stores_and_sends_cookies() {
+    let server = LocalServer::start(|request| match request.target.as_str() {
+        "/consent/" => (200, vec![("Set-Cookie".to_string(), "consent=yes; Path=/".to_string())], String::new()),
+        _ => (200, Vec::new(), "<h1 class=\"headline-1\">Alien</h1>".to_string()),
+    }).await;
+    let jar = CookieJar::new();
+    jar.add("preexisting=1; Path=/", &server.url).unwrap();
+    let client = Client::builder()
+        .base_url(&server.url)
+        .cookie_jar(jar.clone())
+        .build()
+        .unwrap();
+
+    client.get_page(&client.url("consent/")).await.unwrap();
+    client.get_page(&client.url("film/alien/")).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].header("cookie"), Some("preexisting=1"));
+    let cookies = requests[1].header("cookie").unwrap();
+    assert!(cookies.contains("consent=yes") && cookies.contains("preexisting=1"));
+    assert_eq!(jar.get(&server.url, "consent").as_deref(), Some("yes"));
+}
+
+#[tokio::test]
+async fn test_requests_go_through_proxy() {
+    let proxy = LocalServer::start(|_| (200, Vec::new(), "<h1 class=\"headline-1\">Alien</h1>".to_string())).await;
+    let client = Client::builder()
+        .proxy(&proxy.url)
+        .build()
+        .unwrap();
+
+    client.get_page("http://letterboxd.com/film/alien/").await.unwrap();
+
+    let requests = proxy.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].target, "http://letterboxd.com/film/alien/");
+}
+
+#[test]
+fn test_builder_rejects_invalid_proxy_and_certificate() {
+    let invalid_proxy = Client::builder().proxy("not a proxy").build();
+    assert!(matches!(invalid_proxy, Err(Error::InvalidConfig(_))));
+
+    let invalid_certificate = Client::builder().add_root_certificate(b"not a certificate").build();
+    assert!(matches!(invalid_certificate, Err(Error::InvalidConfig(_))));
+}
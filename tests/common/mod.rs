@@ -0,0 +1,138 @@
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use rustboxd::core::{Client, MockTransport};
+
+/// Read an HTML fixture from `tests/fixtures`
+pub fn fixture(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Missing fixture {}: {}", path, e))
+}
+
+/// Client serving the given `(url, fixture name)` pairs from memory
+pub fn mock_client(pages: &[(&str, &str)]) -> (Client, Arc<MockTransport>) {
+    let transport = Arc::new(MockTransport::new());
+    for (url, name) in pages {
+        transport.insert_page(url, &fixture(name));
+    }
+
+    let client = Client::builder()
+        .transport(transport.clone())
+        .build()
+        .unwrap();
+
+    (client, transport)
+}
+
+/// A request received by a [`LocalServer`]
+#[derive(Debug, Clone)]
+pub struct ReceivedRequest {
+    pub method: String,
+    /// Request target as sent, absolute when the server is used as a proxy
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl ReceivedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+type Handler = dyn Fn(&ReceivedRequest) -> (u16, Vec<(String, String)>, String) + Send + Sync;
+
+/// Minimal HTTP/1.1 server on localhost, standing in for Letterboxd over real sockets
+pub struct LocalServer {
+    pub url: String,
+    requests: Arc<std::sync::Mutex<Vec<ReceivedRequest>>>,
+}
+
+impl LocalServer {
+    /// Answer every request with `handler(request) -> (status, headers, body)`
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&ReceivedRequest) -> (u16, Vec<(String, String)>, String) + Send + Sync + 'static,
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve_connection(stream, handler.clone(), received.clone()));
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve_connection(
+    stream: tokio::net::TcpStream,
+    handler: Arc<Handler>,
+    received: Arc<std::sync::Mutex<Vec<ReceivedRequest>>>,
+) {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    let mut stream = BufReader::new(stream);
+    loop {
+        let mut request_line = String::new();
+        if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+            return;
+        }
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let target = parts.next().unwrap_or_default().to_string();
+
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            stream.read_line(&mut line).await.unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_string(), value.trim().to_string()));
+            }
+        }
+
+        let length = headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        stream.read_exact(&mut body).await.unwrap();
+
+        let request = ReceivedRequest {
+            method,
+            target,
+            headers,
+            body: String::from_utf8_lossy(&body).to_string(),
+        };
+        let (status, response_headers, response_body) = handler(&request);
+        received.lock().unwrap().push(request);
+
+        let mut response = format!("HTTP/1.1 {} Status\r\ncontent-length: {}\r\n", status, response_body.len());
+        for (name, value) in response_headers {
+            response.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        response.push_str("\r\n");
+        response.push_str(&response_body);
+
+        if stream.get_mut().write_all(response.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
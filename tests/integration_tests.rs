@@ -1,54 +1,742 @@
-use rustboxd::{User, Movie, Search};
-use tokio_test;
+mod common;
+
+use std::time::Duration;
+use rustboxd::{User, Movie, Search, List, Films, Error};
+use rustboxd::chrono::NaiveDate;
+use rustboxd::models::{CrewRole, FilmStats, MemberFilter, MemberTier, ReleaseType, ReviewQuery, ReviewSort};
+use rustboxd::core::{Client, HttpResponse, MockTransport, RetryPolicy};
+use rustboxd::pages::{UserDiary, UserLists, UserProfile, UserReviews, UserWatchlist};
+use common::mock_client;
 
 #[tokio::test]
 async fn test_user_creation() {
-    let result = User::new("testuser").await;
-    
-    // We expect this to either succeed or fail with a specific error
-    // Since we're testing against a real website, we'll just check the error type
-    match result {
-        Ok(user) => {
-            assert_eq!(user.username, "testuser");
-            assert!(!user.url.is_empty());
-        }
-        Err(e) => {
-            // Expected for non-existent users or network issues
-            println!("Expected error for test user: {}", e);
-        }
-    }
+    let (client, _) = mock_client(&[("https://letterboxd.com/testuser/", "user_profile.html")]);
+
+    let user = User::new_with_client(&client, "TestUser").await.unwrap();
+
+    assert_eq!(user.username, "testuser");
+    assert_eq!(user.display_name, "Test User");
+    assert_eq!(user.bio.as_deref(), Some("Watching everything, slowly."));
+    assert_eq!(user.location.as_deref(), Some("Lisbon"));
+}
+
+#[tokio::test]
+async fn test_user_profile_parsing() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/testuser/", "user_profile.html")]);
+
+    let user = User::new_with_client(&client, "testuser").await.unwrap();
+
+    assert_eq!(user.id, Some(482913));
+    assert_eq!(user.tier, MemberTier::Patron);
+    assert!(!user.is_hq);
+
+    let stats = user.stats.as_ref().unwrap();
+    assert_eq!(stats.films, 1234);
+    assert_eq!(stats.this_year, 87);
+    assert_eq!(stats.lists, 12);
+    assert_eq!(stats.following, 310);
+    assert_eq!(stats.followers, 2500);
+
+    let favorites = user.favorites.as_ref().unwrap();
+    assert_eq!(favorites.len(), 4);
+    assert_eq!(favorites["47756"].name, "Stalker");
+    assert_eq!(favorites["47756"].url, "https://letterboxd.com/film/stalker/");
+
+    let avatar = user.avatar.as_deref().unwrap();
+    assert!(avatar.contains("-0-220-0-220-crop"));
+    assert!(user.avatar_sizes[&1000].contains("-0-1000-0-1000-crop.jpg?v="));
+    assert_eq!(user.avatar_sizes.len(), 5);
+
+    assert_eq!(user.watchlist_length, Some(1021));
+    assert_eq!(user.recent.watchlist, ["stalker", "parasite-2019"]);
+    let march = &user.recent.diary.months["Mar"];
+    assert_eq!(march["15"][0].slug, "the-matrix");
+    assert_eq!(march["2"][0].name, "Stalker");
+}
+
+#[tokio::test]
+async fn test_profile_details() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/testuser/", "user_profile.html")]);
+
+    let profile = UserProfile::new_with_client(&client, "testuser").get_profile().await.unwrap();
+
+    assert_eq!(profile.bio.as_deref(), Some("Watching everything, slowly."));
+    assert_eq!(profile.pronouns.as_deref(), Some("they/them"));
+    assert_eq!(profile.join_date, NaiveDate::from_ymd_opt(2015, 3, 15));
+    assert_eq!(profile.tier, MemberTier::Patron);
+
+    let services: Vec<_> = profile.social_links.iter().map(|link| link.service.as_str()).collect();
+    assert_eq!(services, ["twitter", "instagram"]);
+
+    let favorites: Vec<_> = profile.favorites.iter().map(|film| film.slug.as_str()).collect();
+    assert_eq!(favorites, ["the-matrix", "stalker", "parasite-2019", "heat-1995"]);
+    assert_eq!(profile.favorites[0].film_id, Some(51518));
+
+    assert_eq!(profile.pinned_reviews.len(), 1);
+    let review = &profile.pinned_reviews[0];
+    assert_eq!(review.film_slug.as_deref(), Some("parasite-2019"));
+    assert_eq!(review.film_title.as_deref(), Some("Parasite"));
+    assert_eq!(review.author, "testuser");
+    assert_eq!(review.rating, Some(5.0));
+    assert_eq!(review.body, "The stairs. Always the stairs.");
+    assert_eq!(review.url.as_deref(), Some("https://letterboxd.com/testuser/film/parasite-2019/"));
+
+    assert_eq!(profile.recent_activity.len(), 2);
+    assert_eq!(profile.recent_activity[0].rating, Some(4.5));
+    assert!(profile.recent_activity[0].liked && profile.recent_activity[0].reviewed);
+    assert_eq!(profile.recent_activity[1].rating, None);
+    assert!(!profile.recent_activity[1].liked);
+}
+
+#[tokio::test]
+async fn test_user_not_found() {
+    let (client, _) = mock_client(&[]);
+
+    let result = User::new_with_client(&client, "nobody").await;
+
+    assert!(matches!(result, Err(Error::UserNotFound(ref name)) if name == "nobody"));
 }
 
 #[tokio::test]
 async fn test_movie_creation() {
-    let result = Movie::new("test-movie-slug").await;
-    
-    match result {
-        Ok(movie) => {
-            assert_eq!(movie.slug, "test-movie-slug");
-            assert!(!movie.url.is_empty());
-        }
-        Err(e) => {
-            // Expected for non-existent movies or network issues
-            println!("Expected error for test movie: {}", e);
-        }
+    let (client, _) = mock_client(&[("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html")]);
+
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(movie.slug, "the-matrix");
+    assert_eq!(movie.url, "https://letterboxd.com/film/the-matrix/");
+    assert_eq!(movie.title, "The Matrix");
+    assert_eq!(movie.year, Some(1999));
+    assert_eq!(movie.average_rating, Some(4.23));
+    assert_eq!(movie.rating_count, Some(1834211));
+    assert_eq!(movie.tagline.as_deref(), Some("Believe the unbelievable."));
+    assert_eq!(movie.genres.len(), 2);
+}
+
+#[tokio::test]
+async fn test_movie_metadata() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html")]);
+
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(movie.movie_id, Some(51518));
+    assert_eq!(movie.original_title.as_deref(), Some("Matrix"));
+    assert_eq!(movie.runtime_minutes, Some(136));
+    assert_eq!(movie.imdb_link.as_deref(), Some("http://www.imdb.com/title/tt0133093/maindetails"));
+    assert_eq!(movie.tmdb_link.as_deref(), Some("https://www.themoviedb.org/movie/603/"));
+    assert_eq!(movie.poster.as_deref(), Some("https://a.ltrbxd.com/resized/film-poster/5/1/5/1/8/51518-the-matrix-0-230-0-345-crop.jpg"));
+    assert!(movie.banner.as_deref().is_some_and(|banner| banner.contains("the-matrix-1920")));
+    let trailer = movie.trailer.as_ref().unwrap();
+    assert_eq!(trailer.id, "vKQi3bBA1y8");
+    assert_eq!(trailer.link, "https://www.youtube.com/watch?v=vKQi3bBA1y8");
+    assert_eq!(trailer.embed_url, "https://www.youtube.com/embed/vKQi3bBA1y8");
+    assert_eq!(movie.alternative_titles, ["Matrix", "The Matrix 1", "Матрица"]);
+
+    let details = movie.details.as_ref().unwrap();
+    assert_eq!(details.director, ["Lilly Wachowski", "Lana Wachowski"]);
+    assert_eq!(details.cinematographer, ["Bill Pope"]);
+    assert!(details.writer.is_empty());
+    assert_eq!(details.production_companies, ["Village Roadshow Pictures", "Silver Pictures"]);
+    assert_eq!(details.countries, ["Australia", "USA"]);
+    assert_eq!(details.languages, ["English"]);
+
+    assert_eq!(movie.crew.directors()[1].slug, "lana-wachowski");
+    assert_eq!(movie.crew.get(&CrewRole::Producer)[0].url, "https://letterboxd.com/producer/joel-silver/");
+    assert_eq!(movie.crew.len(), 5);
+
+    assert_eq!(movie.genres[1].slug, "science-fiction");
+    assert_eq!(movie.genres[1].url, "https://letterboxd.com/films/genre/science-fiction/");
+    let themes: Vec<_> = movie.themes.iter().map(|theme| theme.name.as_str()).collect();
+    assert_eq!(themes, ["Epic heroes"]);
+    assert_eq!(movie.mini_themes[0].url, "https://letterboxd.com/films/mini-theme/dystopian-sci-fi/");
+    assert_eq!(movie.nanogenres.len(), 1);
+    assert_eq!(movie.nanogenres[0].name, "Virtual reality, hackers, computers");
+    assert_eq!(movie.nanogenres[0].url, "https://letterboxd.com/films/nanogenre/virtual-reality-hackers-computers/");
+    assert_eq!(movie.cast.len(), 4);
+    assert_eq!(movie.cast[0].name, "Keanu Reeves");
+    assert_eq!(movie.cast[0].role_name.as_deref(), Some("Neo"));
+    assert_eq!(movie.cast[3].role_name, None);
+
+    assert_eq!(movie.popular_reviews.len(), 1);
+    let review = &movie.popular_reviews[0];
+    assert_eq!(review.author, "someone");
+    assert_eq!(review.author_display_name, "Some One");
+    assert_eq!(review.id, Some(412345678));
+    assert_eq!(review.rating, Some(5.0));
+    assert!(review.liked && !review.rewatch);
+    assert_eq!(review.body, "I know kung fu.");
+    assert_eq!(review.like_count, 12345);
+    assert_eq!(review.comment_count, 48);
+    assert_eq!(review.watched_date, NaiveDate::from_ymd_opt(2023, 5, 2));
+}
+
+#[tokio::test]
+async fn test_movie_without_optional_sections() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/film/untitled-short/", "film_minimal.html")]);
+
+    let movie = Movie::new_with_client(&client, "untitled-short").await.unwrap();
+
+    assert_eq!(movie.title, "Untitled Short");
+    assert_eq!(movie.movie_id, Some(998877));
+    assert_eq!(movie.year, Some(2021));
+    assert_eq!(movie.tmdb_link.as_deref(), Some("https://www.themoviedb.org/movie/998877/"));
+    assert_eq!(movie.original_title, None);
+    assert_eq!(movie.runtime_minutes, None);
+    assert_eq!(movie.average_rating, None);
+    assert_eq!(movie.rating_count, None);
+    assert!(movie.genres.is_empty());
+    assert!(movie.themes.is_empty() && movie.mini_themes.is_empty() && movie.nanogenres.is_empty());
+    assert!(movie.crew.is_empty());
+    assert_eq!(movie.imdb_link, None);
+    assert_eq!(movie.poster, None);
+    assert_eq!(movie.banner, None);
+    assert!(movie.trailer.is_none());
+    assert!(movie.details.is_none());
+    assert!(movie.alternative_titles.is_empty());
+    assert!(movie.cast.is_empty());
+    assert!(movie.popular_reviews.is_empty());
+}
+
+#[tokio::test]
+async fn test_movie_legacy_json() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html")]);
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let legacy = movie.to_legacy_json();
+    assert_eq!(legacy["runtime"], "136");
+    assert_eq!(legacy["rating"], "4.2");
+    assert_eq!(legacy["genres"][0], serde_json::json!({ "type": "genre", "name": "Action" }));
+    assert_eq!(legacy["genres"][2], serde_json::json!({ "type": "theme", "name": "Epic heroes" }));
+    assert_eq!(legacy["crew"]["director"][0]["name"], "Lilly Wachowski");
+    assert_eq!(legacy["popular_reviews"][0]["content"], "I know kung fu.");
+
+    let mirror = Client::builder().base_url("https://mirror.test").build().unwrap();
+    let relinked = Movie::from_legacy_json_with_client(&mirror, legacy.clone()).unwrap();
+    assert_eq!(relinked.genres[0].url, "https://mirror.test/films/genre/action/");
+    assert_eq!(relinked.client().base_url(), mirror.base_url());
+
+    let restored = Movie::from_legacy_json(legacy.clone()).unwrap();
+    assert_eq!(restored.runtime_minutes, Some(136));
+    assert_eq!(restored.average_rating, Some(4.2));
+    assert_eq!(restored.genres, movie.genres);
+    assert_eq!(restored.themes[0].slug, "epic-heroes");
+    assert_eq!(restored.mini_themes[0].slug, "dystopian-sci-fi");
+    assert_eq!(restored.nanogenres.len(), 1);
+    assert_eq!(restored.crew, movie.crew);
+    assert_eq!(restored.popular_reviews[0].body, "I know kung fu.");
+    assert_eq!(restored.popular_reviews[0].watched_date, NaiveDate::from_ymd_opt(2023, 5, 2));
+
+    // Dates in other formats are read leniently, and unreadable ones dropped
+    let mut written = legacy.clone();
+    written["popular_reviews"][0]["date"] = serde_json::json!("02 May 2023");
+    let restored = Movie::from_legacy_json(written).unwrap();
+    assert_eq!(restored.popular_reviews[0].watched_date, NaiveDate::from_ymd_opt(2023, 5, 2));
+    for date in ["May 2", "sometime in spring", "2023-13-45T10:00"] {
+        let mut unreadable = legacy.clone();
+        unreadable["popular_reviews"][0]["date"] = serde_json::json!(date);
+        let restored = Movie::from_legacy_json(unreadable).unwrap();
+        assert_eq!(restored.popular_reviews[0].watched_date, None);
     }
+
+    // The current shape reads back unchanged
+    let current = serde_json::to_value(&movie).unwrap();
+    let restored = Movie::from_legacy_json(current).unwrap();
+    assert_eq!(restored.average_rating, movie.average_rating);
+    assert_eq!(restored.themes, movie.themes);
+}
+
+#[tokio::test]
+async fn test_movie_not_found() {
+    let (client, _) = mock_client(&[]);
+
+    let result = Movie::new_with_client(&client, "test-movie-slug").await;
+
+    assert!(matches!(result, Err(Error::MovieNotFound(ref slug)) if slug == "test-movie-slug"));
+}
+
+#[tokio::test]
+async fn test_movie_watchers_walk_every_page() {
+    let (client, transport) = mock_client(&[
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+        ("https://letterboxd.com/film/the-matrix/members/", "film_members.html"),
+        ("https://letterboxd.com/film/the-matrix/members/page/2/", "film_members_page_2.html"),
+    ]);
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let watchers = movie.get_watchers(MemberFilter::Watched, 10).await.unwrap();
+
+    let usernames: Vec<_> = watchers.iter().map(|member| member.username.as_str()).collect();
+    assert_eq!(usernames, ["someone", "quietviewer", "latecomer"]);
+    assert_eq!(watchers[0].display_name, "Some One");
+    assert_eq!(watchers[0].url, "https://letterboxd.com/someone/");
+    assert_eq!(watchers[0].rating, Some(4.5));
+    assert!(watchers[0].liked);
+    assert_eq!(watchers[0].review_url.as_deref(), Some("https://letterboxd.com/someone/film/the-matrix/"));
+    assert_eq!(watchers[1].rating, None);
+    assert!(!watchers[1].liked);
+    assert_eq!(watchers[1].review_url, None);
+    assert_eq!(watchers[2].rating, Some(2.0));
+
+    // Other filters list a different section of the film
+    assert!(movie.get_watchers(MemberFilter::Fans, 10).await.is_err());
+    assert_eq!(transport.request_count("https://letterboxd.com/film/the-matrix/fans/"), 1);
+}
+
+#[tokio::test]
+async fn test_movie_watchers_stop_at_limit() {
+    let (client, transport) = mock_client(&[
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+        ("https://letterboxd.com/film/the-matrix/members/", "film_members.html"),
+        ("https://letterboxd.com/film/the-matrix/members/page/2/", "film_members_page_2.html"),
+    ]);
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let watchers = movie.get_watchers(MemberFilter::Watched, 2).await.unwrap();
+
+    assert_eq!(watchers.len(), 2);
+    assert_eq!(transport.request_count("https://letterboxd.com/film/the-matrix/members/page/2/"), 0);
+}
+
+#[tokio::test]
+async fn test_films_stop_at_limit() {
+    let (client, transport) = mock_client(&[
+        ("https://letterboxd.com/ajax/films/popular/page/1/", "films_popular.html"),
+        ("https://letterboxd.com/ajax/films/popular/page/2/", "films_popular.html"),
+    ]);
+
+    let films = Films::new_with_client(&client, "https://letterboxd.com/films/popular/", 2).await.unwrap();
+
+    assert_eq!(films.count, 2);
+    assert!(films.movies.contains_key("the-matrix"));
+    assert_eq!(transport.request_count("https://letterboxd.com/ajax/films/popular/page/2/"), 0);
+}
+
+#[tokio::test]
+async fn test_movie_member_counts() {
+    let (client, _) = mock_client(&[
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+        ("https://letterboxd.com/csi/film/the-matrix/stats/", "film_stats.html"),
+    ]);
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let counts = movie.get_member_counts().await.unwrap();
+
+    assert_eq!(counts.watched, Some(3912345));
+    assert_eq!(counts.listed, Some(512345));
+    // Without a tooltip the rounded count is used
+    assert_eq!(counts.liked, Some(1200000));
+}
+
+#[tokio::test]
+async fn test_movie_member_counts_unknown_when_unreadable() {
+    let (client, transport) = mock_client(&[("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html")]);
+    transport.insert_page(
+        "https://letterboxd.com/csi/film/the-matrix/stats/",
+        r#"<ul class="film-stats"><li class="stat filmstat-watches"><a href="/film/the-matrix/members/">—</a></li></ul>"#,
+    );
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let counts = movie.get_member_counts().await.unwrap();
+
+    assert_eq!(counts.watched, None);
+    assert_eq!(counts.listed, None);
+}
+
+#[tokio::test]
+async fn test_movie_reviews_sorted_and_paginated() {
+    let (client, transport) = mock_client(&[
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+        ("https://letterboxd.com/film/the-matrix/reviews/by/added/", "film_reviews.html"),
+        ("https://letterboxd.com/film/the-matrix/reviews/by/added/page/2/", "film_reviews_page_2.html"),
+    ]);
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let reviews = movie.get_reviews(ReviewQuery::new().sort(ReviewSort::Recent), 10).await.unwrap();
+
+    let authors: Vec<_> = reviews.iter().map(|review| review.author.as_str()).collect();
+    assert_eq!(authors, ["nightowl", "quietviewer", "someone"]);
+
+    let rewatch = &reviews[0];
+    assert_eq!(rewatch.id, Some(600000002));
+    assert_eq!(rewatch.url.as_deref(), Some("https://letterboxd.com/nightowl/film/the-matrix/1/"));
+    assert_eq!(rewatch.film_slug.as_deref(), Some("the-matrix"));
+    assert!(rewatch.rewatch && !rewatch.liked);
+    assert!(rewatch.contains_spoilers);
+    assert_eq!(rewatch.body, "He is the one.\n\nStill great.");
+    assert!(rewatch.body_html.contains("<em>is</em>"));
+    assert!(!rewatch.body_html.contains("spoilers"));
+    assert_eq!(rewatch.watched_date, NaiveDate::from_ymd_opt(2024, 3, 16));
+
+    let bare = &reviews[1];
+    assert_eq!(bare.rating, None);
+    assert_eq!(bare.watched_date, None);
+    assert_eq!((bare.like_count, bare.comment_count), (0, 0));
+    assert!(!bare.contains_spoilers);
+
+    // Filters and orders are part of the listing's URL
+    let query = ReviewQuery::new().rating(4.5).sort(ReviewSort::Highest);
+    assert!(movie.get_reviews(query, 10).await.is_err());
+    assert_eq!(transport.request_count("https://letterboxd.com/film/the-matrix/reviews/rated/4.5/by/entry-rating/"), 1);
+    let query = ReviewQuery::new().friends_only(true);
+    assert!(movie.get_reviews(query, 10).await.is_err());
+    assert_eq!(transport.request_count("https://letterboxd.com/film/the-matrix/friends/reviews/"), 1);
+
+    assert!(matches!(movie.reviews(ReviewQuery::new().rating(4.3)), Err(Error::InvalidInput(_))));
+
+    // The limit stops before the second page is requested
+    let first = movie.get_reviews(ReviewQuery::new().sort(ReviewSort::Recent), 1).await.unwrap();
+    assert_eq!(first.len(), 1);
+    assert_eq!(transport.request_count("https://letterboxd.com/film/the-matrix/reviews/by/added/page/2/"), 1);
+}
+
+#[tokio::test]
+async fn test_movie_similar_films() {
+    let (client, _) = mock_client(&[
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+        ("https://letterboxd.com/film/the-matrix/similar/", "film_similar.html"),
+    ]);
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let similar = movie.get_similar().await.unwrap();
+
+    let slugs: Vec<_> = similar.iter().map(|film| film.slug.as_str()).collect();
+    assert_eq!(slugs, ["dark-city", "the-thirteenth-floor", "existenz"]);
+    assert_eq!(similar[0].name, "Dark City");
+    assert_eq!(similar[0].url, "https://letterboxd.com/film/dark-city/");
+    assert_eq!(similar[0].film_id, Some(2526));
+    assert_eq!(similar[0].year, Some(1998));
+    // Without a name attribute the poster's alt text is used
+    assert_eq!(similar[2].name, "eXistenZ");
+    assert_eq!(similar[2].year, None);
+}
+
+#[tokio::test]
+async fn test_movie_stats() {
+    let histogram_url = "https://letterboxd.com/csi/film/the-matrix/rating-histogram/";
+    let (client, transport) = mock_client(&[
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+        (histogram_url, "film_rating_histogram.html"),
+        ("https://letterboxd.com/csi/film/the-matrix/stats/", "film_stats.html"),
+    ]);
+    // Served on the second fetch
+    transport.insert_page(histogram_url, &common::fixture("film_rating_histogram.html").replace("86K&nbsp;fans", "—&nbsp;fans"));
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let stats = movie.get_stats().await.unwrap();
+
+    assert_eq!(stats.histogram, [0, 10, 5, 25, 20, 90, 100, 250, 200, 1300]);
+    assert_eq!(stats.average_rating, Some(4.23));
+    assert_eq!(stats.rating_count, 2000);
+    assert_eq!(stats.fans, Some(86000));
+    assert_eq!(stats.members.watched, Some(3912345));
+    assert_eq!(stats.top_250_rank, Some(12));
+
+    let mean = stats.mean_rating().unwrap();
+    assert!((mean - 4.56875).abs() < 1e-4);
+    // A heavy prior pulls the score towards the prior mean
+    let adjusted = stats.bayesian_average(3.0, 2000).unwrap();
+    assert!((adjusted - 3.784375).abs() < 1e-4);
+    assert_eq!(FilmStats::default().mean_rating(), None);
+
+    // An unreadable fan count is unknown, not zero
+    assert_eq!(movie.get_stats().await.unwrap().fans, None);
+}
+
+#[tokio::test]
+async fn test_movie_releases() {
+    let (client, _) = mock_client(&[
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+        ("https://letterboxd.com/film/the-matrix/releases/", "film_releases.html"),
+    ]);
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    let releases = movie.get_releases().await.unwrap();
+
+    assert_eq!(releases.len(), 5);
+    assert_eq!(releases[0].release_type, ReleaseType::Premiere);
+    assert_eq!(releases[0].date, NaiveDate::from_ymd_opt(1999, 3, 24).unwrap());
+    assert_eq!(releases[0].note.as_deref(), Some("Westwood premiere"));
+    assert_eq!(releases[0].certification, None);
+
+    let theatrical: Vec<_> = releases.iter()
+        .filter(|release| release.release_type == ReleaseType::Theatrical)
+        .map(|release| (release.country.as_str(), release.certification.as_deref()))
+        .collect();
+    assert_eq!(theatrical, [("USA", Some("R")), ("Canada", Some("14A")), ("UK", Some("15"))]);
+    assert_eq!(releases[3].date, NaiveDate::from_ymd_opt(1999, 6, 11).unwrap());
+
+    assert_eq!(releases[4].release_type, ReleaseType::Physical);
+    assert_eq!(releases[4].note.as_deref(), Some("DVD"));
 }
 
 #[tokio::test]
 async fn test_search_creation() {
-    let result = Search::new("test query", Some("films")).await;
-    
-    match result {
-        Ok(search) => {
-            assert_eq!(search.query, "test query");
-            assert_eq!(search.search_filter, Some("films".to_string()));
-        }
-        Err(e) => {
-            // Expected for network issues
-            println!("Expected error for test search: {}", e);
-        }
-    }
+    let (client, _) = mock_client(&[("https://letterboxd.com/s/search/films/test%20query/", "search_films.html")]);
+
+    let search = Search::new_with_client(&client, "test query", Some("films")).await.unwrap();
+
+    assert_eq!(search.query, "test query");
+    assert_eq!(search.search_filter, Some("films".to_string()));
+    assert_eq!(search.results.films.len(), 2);
+    assert_eq!(search.results.films[0].slug, "blade-runner");
+    assert_eq!(search.results.films[0].year, Some(1982));
+    assert_eq!(search.results.films[1].url, "https://letterboxd.com/film/blade-runner-2049/");
+}
+
+#[tokio::test]
+async fn test_list_parsing() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/testuser/list/favourite-heists/", "list_page.html")]);
+
+    let list = List::new_with_client(&client, "testuser", "favourite-heists").await.unwrap();
+
+    assert_eq!(list.title, "Favourite Heists");
+    assert_eq!(list.film_count, 3);
+    assert_eq!(list.likes, 1200);
+    assert_eq!(list.comments, 45);
+    assert_eq!(list.tags, vec!["heist", "crime"]);
+    assert_eq!(list.get_film_by_position(2).map(|f| f.slug.as_str()), Some("rififi"));
+    assert_eq!(list.list_id, Some(4321));
+    assert!(list.is_ranked);
+    assert!(list.is_public);
+    assert_eq!(list.description.as_deref(), Some("Crews, plans and things going wrong."));
+    assert_eq!(list.films[0].film_id, Some(1001));
+    assert_eq!(list.films[0].notes, None);
+    assert_eq!(list.films[1].notes.as_deref(), Some("The silent break-in.\n\nThirty minutes without a word."));
+}
+
+#[tokio::test]
+async fn test_list_reads_every_page() {
+    let (client, _) = mock_client(&[
+        ("https://letterboxd.com/testuser/list/watch-next/", "list_private.html"),
+        ("https://letterboxd.com/testuser/list/watch-next/page/2/", "list_private_page_2.html"),
+    ]);
+
+    let list = List::new_with_client(&client, "testuser", "watch-next").await.unwrap();
+
+    assert!(!list.is_public);
+    assert_eq!(list.title, "Watch Next");
+    assert_eq!(list.film_count, 3);
+    let films: Vec<_> = list.films.iter().map(|f| (f.slug.as_str(), f.position)).collect();
+    assert_eq!(films, [("stalker", Some(1)), ("solaris", Some(2)), ("mirror", Some(3))]);
+    assert_eq!(list.films[2].notes.as_deref(), Some("Finally."));
+}
+
+#[tokio::test]
+async fn test_watchlist_parsing() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/testuser/watchlist/", "user_watchlist.html")]);
+
+    let movies = UserWatchlist::new_with_client(&client, "testuser").get_watchlist_movies().await.unwrap();
+
+    assert_eq!(movies.len(), 2);
+    assert_eq!(movies["stalker"].name, "Stalker");
+    assert_eq!(movies["parasite-2019"].url, "https://letterboxd.com/film/parasite-2019/");
+}
+
+#[tokio::test]
+async fn test_diary_entries_reuse_client() {
+    let (client, transport) = mock_client(&[
+        ("https://letterboxd.com/testuser/films/diary/", "user_diary.html"),
+        ("https://letterboxd.com/film/the-matrix/", "film_the_matrix.html"),
+    ]);
+
+    let entries = UserDiary::new_with_client(&client, "testuser").get_diary_entries().await.unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!((entries[0].month, entries[0].day), (3, 15));
+    assert_eq!(entries[0].date, NaiveDate::from_ymd_opt(2024, 3, 15));
+    assert_eq!(entries[0].year, Some(1999));
+    // Enrichment went through the injected client as well
+    assert_eq!(transport.request_count("https://letterboxd.com/film/the-matrix/"), 1);
+    assert_eq!(transport.request_count("https://letterboxd.com/film/stalker/"), 1);
+}
+
+#[tokio::test]
+async fn test_diary_walks_every_page() {
+    let (client, transport) = mock_client(&[
+        ("https://letterboxd.com/testuser/films/diary/for/2024/", "user_diary_2024.html"),
+        ("https://letterboxd.com/testuser/films/diary/for/2024/page/2/", "user_diary_2024_page_2.html"),
+    ]);
+    let diary = UserDiary::new_with_client(&client, "testuser");
+
+    let entries = diary.get_year(2024).await.unwrap();
+
+    let slugs: Vec<_> = entries.iter().map(|e| e.film_slug.as_str()).collect();
+    assert_eq!(slugs, ["the-matrix", "stalker", "parasite-2019"]);
+
+    let matrix = &entries[0];
+    assert_eq!(matrix.entry_id, Some(516340105));
+    assert_eq!(matrix.date, NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+    assert_eq!(matrix.film_title, "The Matrix");
+    assert_eq!(matrix.film_year, Some(1999));
+    assert_eq!(matrix.film_id, Some(51518));
+    assert_eq!(matrix.rating, Some(4.5));
+    assert!(matrix.liked && matrix.rewatch && matrix.has_review);
+    assert_eq!(matrix.review_url.as_deref(), Some("https://letterboxd.com/testuser/film/the-matrix/"));
+    assert_eq!(matrix.tags, ["rewatch-club", "cinema"]);
+
+    let stalker = &entries[1];
+    assert_eq!(stalker.date, NaiveDate::from_ymd_opt(2024, 3, 2).unwrap());
+    assert_eq!(stalker.rating, None);
+    assert!(!stalker.liked && !stalker.rewatch && !stalker.has_review);
+
+    assert_eq!(entries[2].date, NaiveDate::from_ymd_opt(2024, 1, 7).unwrap());
+
+    // A single page only fetches that page
+    let page = diary.get_diary_page(Some(2024), None, None, 2).await.unwrap();
+    assert_eq!(page.entries.len(), 1);
+    assert!(!page.has_next);
+    assert_eq!(transport.request_count("https://letterboxd.com/testuser/films/diary/for/2024/"), 1);
+}
+
+#[tokio::test]
+async fn test_user_lists_parsing() {
+    let (client, _) = mock_client(&[("https://letterboxd.com/testuser/lists/", "user_lists.html")]);
+
+    let lists = UserLists::new_with_client(&client, "testuser").get_lists().await.unwrap();
+
+    assert_eq!(lists.len(), 2);
+    assert_eq!(lists["favourite-heists"]["name"], "Favourite Heists");
+    assert_eq!(lists["favourite-heists"]["list_id"], 4321);
+    assert_eq!(lists["favourite-heists"]["url"], "https://letterboxd.com/testuser/list/favourite-heists/");
+    assert_eq!(lists["slow-cinema"]["film_count"], 1204);
+}
+
+#[tokio::test]
+async fn test_user_reviews_walk_every_page() {
+    let (client, _) = mock_client(&[
+        ("https://letterboxd.com/testuser/films/reviews/", "user_reviews.html"),
+        ("https://letterboxd.com/testuser/films/reviews/page/2/", "user_reviews_page_2.html"),
+    ]);
+
+    let reviews = UserReviews::new_with_client(&client, "testuser").get_reviews().await.unwrap();
+
+    assert_eq!(reviews.len(), 2);
+    let heat = &reviews[1];
+    assert_eq!(heat.url.as_deref(), Some("https://letterboxd.com/testuser/film/heat-1995/"));
+    assert_eq!(heat.film_slug.as_deref(), Some("heat-1995"));
+    assert_eq!(heat.film_title.as_deref(), Some("Heat"));
+    assert_eq!(heat.rating, Some(3.5));
+    assert_eq!(heat.author, "testuser");
+    assert_eq!(heat.body, "The diner scene.");
+}
+
+#[tokio::test]
+async fn test_movies_are_cached_across_client_clones() {
+    let url = "https://letterboxd.com/film/the-matrix/";
+    let (client, transport) = mock_client(&[(url, "film_the_matrix.html")]);
+
+    let clone = client.clone();
+    let (a, b) = tokio::join!(
+        tokio::spawn(async move { Movie::new_with_client(&clone, "the-matrix").await }),
+        Movie::new_with_client(&client, "the-matrix"),
+    );
+    let again = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(a.unwrap().unwrap().title, b.unwrap().title);
+    assert_eq!(again.title, "The Matrix");
+    assert_eq!(transport.request_count(url), 1);
+    assert_eq!(client.movie_cache().map(|c| c.len()), Some(1));
+
+    Movie::new_with_client(&client.bypass_cache(), "the-matrix").await.unwrap();
+    assert_eq!(transport.request_count(url), 2);
+}
+
+#[tokio::test]
+async fn test_movie_cache_can_be_disabled() {
+    let url = "https://letterboxd.com/film/the-matrix/";
+    let transport = std::sync::Arc::new(MockTransport::new().with_page(url, &common::fixture("film_the_matrix.html")));
+    let client = Client::builder()
+        .transport(transport.clone())
+        .movie_cache_capacity(0)
+        .build()
+        .unwrap();
+
+    Movie::new_with_client(&client, "the-matrix").await.unwrap();
+    Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert!(client.movie_cache().is_none());
+    assert_eq!(transport.request_count(url), 2);
+}
+
+#[tokio::test]
+async fn test_retry_recovers_from_transient_errors() {
+    let url = "https://letterboxd.com/film/the-matrix/";
+    let transport = std::sync::Arc::new(MockTransport::new()
+        .with_response(url, HttpResponse::new(url, 503, "Service Unavailable"))
+        .with_response(url, HttpResponse::new(url, 429, "Slow down").with_header("Retry-After", "0"))
+        .with_page(url, &common::fixture("film_the_matrix.html")));
+    let client = Client::builder()
+        .transport(transport.clone())
+        .retry(RetryPolicy::new(3).base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    let movie = Movie::new_with_client(&client, "the-matrix").await.unwrap();
+
+    assert_eq!(movie.title, "The Matrix");
+    assert_eq!(transport.request_count(url), 3);
+}
+
+#[tokio::test]
+async fn test_retry_after_on_503_is_capped_at_max_delay() {
+    let url = "https://letterboxd.com/film/the-matrix/";
+    let transport = MockTransport::new()
+        .with_response(url, HttpResponse::new(url, 503, "Service Unavailable").with_header("Retry-After", "86400"))
+        .with_page(url, &common::fixture("film_the_matrix.html"));
+    let client = Client::builder()
+        .transport(transport)
+        .retry(RetryPolicy::new(2).max_delay(Duration::from_millis(5)))
+        .build()
+        .unwrap();
+
+    let page = tokio::time::timeout(Duration::from_secs(5), client.get_page(url)).await;
+
+    assert!(page.unwrap().is_ok());
+}
+
+#[tokio::test]
+async fn test_503_exposes_retry_after() {
+    let url = "https://letterboxd.com/film/the-matrix/";
+    let transport = MockTransport::new()
+        .with_response(url, HttpResponse::new(url, 503, "Service Unavailable").with_header("Retry-After", "120"));
+    let client = Client::builder().transport(transport).build().unwrap();
+
+    let error = client.get_page(url).await.unwrap_err();
+
+    assert_eq!(error.status(), Some(503));
+    assert_eq!(error.retry_after(), Some(Duration::from_secs(120)));
+}
+
+#[tokio::test]
+async fn test_retry_reports_attempts_when_exhausted() {
+    let url = "https://letterboxd.com/film/the-matrix/";
+    let transport = MockTransport::new()
+        .with_response(url, HttpResponse::new(url, 502, "Bad Gateway"));
+    let client = Client::builder()
+        .transport(transport)
+        .retry(RetryPolicy::new(2).base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    let error = client.get_page(url).await.unwrap_err();
+
+    assert!(matches!(error, Error::RetriesExhausted { attempts: 2, .. }));
+    assert_eq!(error.status(), Some(502));
+}
+
+#[tokio::test]
+async fn test_not_found_is_not_retried() {
+    let transport = std::sync::Arc::new(MockTransport::new());
+    let client = Client::builder()
+        .transport(transport.clone())
+        .retry(RetryPolicy::new(5).base_delay(Duration::from_millis(1)))
+        .build()
+        .unwrap();
+
+    let error = client.get_page("https://letterboxd.com/film/missing/").await.unwrap_err();
+
+    assert!(matches!(error, Error::NotFound { .. }));
+    assert_eq!(transport.requests().len(), 1);
 }
 
 #[test]
@@ -56,9 +744,43 @@ fn test_invalid_search_filter() {
     // Test that invalid search filters are rejected at compile time or runtime
     // This test doesn't need to make network requests
     let valid_filters = ["films", "reviews", "lists", "members"];
-    
+
     for filter in valid_filters {
         // These should be valid
         assert!(rustboxd::core::constants::SEARCH_FILTERS.contains(&filter));
     }
 }
+
+#[test]
+fn test_client_builder_base_url() {
+    let client = rustboxd::Client::builder()
+        .base_url("http://localhost:8080/")
+        .build()
+        .unwrap();
+
+    assert_eq!(client.base_url(), "http://localhost:8080");
+    assert_eq!(client.url("/film/the-matrix/"), "http://localhost:8080/film/the-matrix/");
+}
+
+#[test]
+fn test_client_builder_rejects_invalid_config() {
+    let invalid_url = rustboxd::Client::builder().base_url("not a url").build();
+    assert!(matches!(invalid_url, Err(rustboxd::Error::UrlParse(_))));
+
+    let invalid_header = rustboxd::Client::builder().header("bad header", "value").build();
+    assert!(matches!(invalid_header, Err(rustboxd::Error::InvalidConfig(_))));
+}
+
+#[tokio::test]
+async fn test_following_follows_pagination() {
+    let (client, _) = mock_client(&[
+        ("https://letterboxd.com/testuser/following/", "user_following.html"),
+        ("https://letterboxd.com/testuser/following/page/2/", "user_following_page_2.html"),
+    ]);
+
+    let following = rustboxd::pages::UserNetwork::new_with_client(&client, "testuser").get_following().await.unwrap();
+
+    assert_eq!(following.len(), 3);
+    assert_eq!(following["carol"]["display_name"], "Carol Reed");
+    assert_eq!(following["dave"]["url"], "https://letterboxd.com/dave/");
+}
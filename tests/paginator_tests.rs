@@ -0,0 +1,98 @@
+use std::sync::Arc;
+use futures::{StreamExt, TryStreamExt};
+use scraper::Selector;
+use rustboxd::core::{Client, HttpResponse, MockTransport, Paginator};
+use rustboxd::Error;
+
+const LISTING: &str = "https://letterboxd.com/testuser/things/";
+
+/// A listing whose pages hold the given items; every page but the last links to the next
+fn listing(pages: &[&[&str]]) -> (Client, Arc<MockTransport>) {
+    listing_on(Arc::new(MockTransport::new()), pages)
+}
+
+/// Like [`listing`], on a transport that may already have responses queued
+fn listing_on(transport: Arc<MockTransport>, pages: &[&[&str]]) -> (Client, Arc<MockTransport>) {
+    for (index, items) in pages.iter().enumerate() {
+        let page = index + 1;
+        let url = if page == 1 { LISTING.to_string() } else { format!("{}page/{}/", LISTING, page) };
+        let items: String = items.iter().map(|item| format!("<li class=\"item\">{}</li>", item)).collect();
+        let next = if page < pages.len() {
+            format!("<div class=\"paginate-nextprev\"><a class=\"next\" href=\"/testuser/things/page/{}/\">Older</a></div>", page + 1)
+        } else {
+            String::new()
+        };
+        transport.insert_page(&url, &format!("<html><body><ul>{}</ul>{}</body></html>", items, next));
+    }
+
+    let client = Client::builder().transport(transport.clone()).build().unwrap();
+    (client, transport)
+}
+
+fn things(client: &Client) -> Paginator<String> {
+    Paginator::for_url(client, LISTING, |dom, _| {
+        let selector = Selector::parse("li.item").unwrap();
+        Ok(dom.select(&selector).map(|li| li.text().collect()).collect())
+    })
+}
+
+#[tokio::test]
+async fn test_follows_next_links_whatever_the_page_size() {
+    let (client, transport) = listing(&[&["a", "b", "c"], &["d"], &["e", "f"]]);
+
+    let items: Vec<String> = things(&client).try_collect().await.unwrap();
+
+    assert_eq!(items, ["a", "b", "c", "d", "e", "f"]);
+    // Page 3 has no next link, so page 4 is never requested
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_take_stops_fetching_pages() {
+    let (client, transport) = listing(&[&["a", "b"], &["c", "d"], &["e", "f"]]);
+
+    let items: Vec<String> = things(&client).take(3).try_collect().await.unwrap();
+
+    assert_eq!(items, ["a", "b", "c"]);
+    assert_eq!(transport.request_count(&format!("{}page/3/", LISTING)), 0);
+}
+
+#[tokio::test]
+async fn test_resume_from_page() {
+    let (client, transport) = listing(&[&["a", "b"], &["c", "d"], &["e"]]);
+
+    let mut paginator = things(&client).take(3);
+    while paginator.next().await.is_some() {}
+    let page = paginator.get_ref().page().unwrap();
+    assert_eq!(page, 2);
+
+    let rest: Vec<String> = things(&client).start_page(page).try_collect().await.unwrap();
+
+    assert_eq!(rest, ["c", "d", "e"]);
+    assert_eq!(transport.request_count(LISTING), 1);
+}
+
+#[tokio::test]
+async fn test_prefetch_discards_pages_past_the_end() {
+    let (client, transport) = listing(&[&["a"], &["b"]]);
+
+    let items: Vec<String> = things(&client).prefetch(3).try_collect().await.unwrap();
+
+    // Pages requested ahead of the last one don't exist, and their errors are not returned
+    assert_eq!(items, ["a", "b"]);
+    assert_eq!(transport.request_count(LISTING), 1);
+}
+
+#[tokio::test]
+async fn test_stream_ends_after_an_error() {
+    let page_2 = format!("{}page/2/", LISTING);
+    let transport = Arc::new(MockTransport::new().with_response(&page_2, HttpResponse::new(&page_2, 404, "")));
+    let (client, transport) = listing_on(transport, &[&["a"], &["b"], &["c"]]);
+
+    let results: Vec<_> = things(&client).collect().await;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].as_ref().unwrap(), "a");
+    assert!(matches!(results[1], Err(Error::NotFound { .. })));
+    assert_eq!(transport.request_count(&format!("{}page/3/", LISTING)), 0);
+}
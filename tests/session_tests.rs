@@ -0,0 +1,409 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use rustboxd::{Client, Error, List};
+use rustboxd::chrono::NaiveDate;
+use rustboxd::session::{ListDraft, LogEntry};
+use common::{LocalServer, ReceivedRequest};
+
+type Response = (u16, Vec<(String, String)>, String);
+
+/// A stand-in for the Letterboxd sign-in flow.
+///
+/// Only `alice` / `secret` can sign in. The session cookie is the current
+/// `generation`, so bumping it expires every session issued before.
+async fn start_site(generation: Arc<AtomicUsize>) -> LocalServer {
+    start_site_with(generation, |_| None).await
+}
+
+/// The sign-in stand-in, answering other requests from a signed-in user with `routes`
+async fn start_site_with<F>(generation: Arc<AtomicUsize>, routes: F) -> LocalServer
+where
+    F: Fn(&ReceivedRequest) -> Option<Response> + Send + Sync + 'static,
+{
+    LocalServer::start(move |request: &ReceivedRequest| {
+        let cookie = |name: &str| request.header("cookie")
+            .and_then(|cookies| cookies.split("; ").find_map(|c| c.strip_prefix(&format!("{}=", name))))
+            .map(str::to_string);
+
+        match (request.method.as_str(), request.target.as_str()) {
+            ("GET", "/sign-in/") => (
+                200,
+                vec![("Set-Cookie".to_string(), "com.xk72.webparts.csrf=token-1; Path=/".to_string())],
+                r#"<form><input type="hidden" name="__csrf" value="token-1"></form>"#.to_string(),
+            ),
+            ("POST", "/user/login.do") => {
+                let form: HashMap<String, String> = url::form_urlencoded::parse(request.body.as_bytes()).into_owned().collect();
+                if form.get("__csrf").map(String::as_str) != Some("token-1") || cookie("com.xk72.webparts.csrf").as_deref() != Some("token-1") {
+                    return (403, Vec::new(), "CSRF mismatch".to_string());
+                }
+                if form["username"] != "alice" || form["password"] != "secret" {
+                    return (200, Vec::new(), r#"{"result":"error","messages":["Your credentials don't match."]}"#.to_string());
+                }
+
+                (
+                    200,
+                    vec![("Set-Cookie".to_string(), format!("letterboxd.user.CURRENT={}; Path=/", generation.load(Ordering::SeqCst)))],
+                    r#"{"result":"success","csrf":"token-2"}"#.to_string(),
+                )
+            }
+            ("GET", "/activity/") => {
+                if cookie("letterboxd.user.CURRENT") == Some(generation.load(Ordering::SeqCst).to_string()) {
+                    (200, Vec::new(), "<h1>Your activity</h1>".to_string())
+                } else {
+                    (302, vec![("Location".to_string(), "/sign-in/".to_string())], String::new())
+                }
+            }
+            // Never accessible, whatever the session
+            ("GET", "/members-only/") => (302, vec![("Location".to_string(), "/sign-in/".to_string())], String::new()),
+            // Blocked whatever the session
+            ("GET", "/blocked-member/") => (403, Vec::new(), String::new()),
+            _ if cookie("letterboxd.user.CURRENT") != Some(generation.load(Ordering::SeqCst).to_string()) => {
+                (401, Vec::new(), String::new())
+            }
+            _ => routes(request).unwrap_or((404, Vec::new(), String::new())),
+        }
+    }).await
+}
+
+fn client_for(server: &LocalServer) -> Client {
+    Client::builder().base_url(&server.url).build().unwrap()
+}
+
+fn logins(server: &LocalServer) -> usize {
+    server.requests().iter().filter(|r| r.target == "/user/login.do").count()
+}
+
+#[tokio::test]
+async fn test_login_stores_session_and_csrf() {
+    let server = start_site(Arc::new(AtomicUsize::new(1))).await;
+    let client = client_for(&server);
+
+    let session = client.login("alice", "secret").await.unwrap();
+
+    assert_eq!(session.username(), "alice");
+    assert_eq!(session.csrf_token(), "token-2");
+    assert_eq!(client.cookie_jar().get(&server.url, "letterboxd.user.CURRENT").as_deref(), Some("1"));
+
+    let page = session.get_page(&client.url("activity/")).await.unwrap();
+    assert!(page.html().contains("Your activity"));
+    // Plain clients share the session cookie too
+    assert!(client.get_page(&client.url("activity/")).await.unwrap().html().contains("Your activity"));
+}
+
+#[tokio::test]
+async fn test_login_with_wrong_password() {
+    let server = start_site(Arc::new(AtomicUsize::new(1))).await;
+
+    let result = client_for(&server).login("alice", "wrong").await;
+
+    assert!(matches!(result, Err(Error::LoginFailed(ref message)) if message == "Your credentials don't match."));
+}
+
+#[tokio::test]
+async fn test_expired_session_signs_in_again() {
+    let generation = Arc::new(AtomicUsize::new(1));
+    let server = start_site(generation.clone()).await;
+    let client = client_for(&server);
+    let session = client.login("alice", "secret").await.unwrap();
+
+    generation.store(2, Ordering::SeqCst);
+    let page = session.get_page(&client.url("activity/")).await.unwrap();
+
+    assert!(page.html().contains("Your activity"));
+    assert_eq!(logins(&server), 2);
+}
+
+#[tokio::test]
+async fn test_session_expired_when_signing_in_again_does_not_help() {
+    let server = start_site(Arc::new(AtomicUsize::new(1))).await;
+    let client = client_for(&server);
+    let session = client.login("alice", "secret").await.unwrap();
+
+    let result = session.get_page(&client.url("members-only/")).await;
+
+    assert!(matches!(result, Err(Error::SessionExpired)));
+    assert_eq!(logins(&server), 2);
+}
+
+#[tokio::test]
+async fn test_forbidden_page_does_not_sign_in_again() {
+    let server = start_site(Arc::new(AtomicUsize::new(1))).await;
+    let client = client_for(&server);
+    let session = client.login("alice", "secret").await.unwrap();
+
+    let result = session.get_page(&client.url("blocked-member/")).await;
+
+    assert!(matches!(result, Err(Error::PrivateRoute)));
+    assert_eq!(logins(&server), 1);
+}
+
+#[tokio::test]
+async fn test_unauthorized_after_signing_in_again_returns_its_status() {
+    let generation = Arc::new(AtomicUsize::new(1));
+    let server = start_site_with(generation.clone(), |request| {
+        (request.target == "/settings/").then(|| (401, Vec::new(), String::new()))
+    }).await;
+    let client = client_for(&server);
+    let session = client.login("alice", "secret").await.unwrap();
+
+    let result = session.get_page(&client.url("settings/")).await;
+
+    assert!(matches!(result, Err(Error::HttpStatus { status: 401, .. })));
+    assert_eq!(logins(&server), 2);
+}
+
+/// Site with The Matrix film page, accepting every form under `/s/` unless it sets `fail=true`
+async fn start_film_site() -> LocalServer {
+    start_site_with(Arc::new(AtomicUsize::new(1)), |request| {
+        if request.target == "/film/the-matrix/" {
+            return Some((200, Vec::new(), common::fixture("film_the_matrix.html")));
+        }
+        if request.method != "POST" || !request.target.starts_with("/s/") {
+            return None;
+        }
+        let form = form_fields(request);
+        if form.get("__csrf").map(String::as_str) != Some("token-2") {
+            return Some((200, Vec::new(), r#"{"result":false,"messages":["Invalid CSRF token."]}"#.to_string()));
+        }
+        if form.get("review").is_some_and(|review| review == "fail") {
+            return Some((200, Vec::new(), r#"{"result":false,"messages":["That film is not available."]}"#.to_string()));
+        }
+        Some((200, Vec::new(), r#"{"result":true,"viewingId":9001}"#.to_string()))
+    }).await
+}
+
+fn form_fields(request: &ReceivedRequest) -> HashMap<String, String> {
+    url::form_urlencoded::parse(request.body.as_bytes()).into_owned().collect()
+}
+
+fn posts(server: &LocalServer, target: &str) -> Vec<HashMap<String, String>> {
+    server.requests().iter().filter(|r| r.target == target).map(form_fields).collect()
+}
+
+#[tokio::test]
+async fn test_log_film_submits_diary_form() {
+    let server = start_film_site().await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+    let entry = LogEntry::new("the-matrix", NaiveDate::from_ymd_opt(2024, 3, 15).unwrap())
+        .rating(4.5)
+        .liked(true)
+        .review("Still holds up.")
+        .tags(&["cinema", "rewatch club"]);
+
+    let confirmation = session.log_film(&entry).await.unwrap();
+
+    assert_eq!(confirmation.film_id, 51518);
+    assert_eq!(confirmation.viewing_id, Some(9001));
+    assert_eq!(confirmation.rating, Some(4.5));
+    let form = &posts(&server, "/s/save-diary-entry")[0];
+    assert_eq!(form["filmId"], "51518");
+    assert_eq!(form["viewingDateStr"], "2024-03-15");
+    assert_eq!(form["rating"], "9");
+    assert_eq!(form["liked"], "true");
+    assert_eq!(form["rewatch"], "false");
+    assert_eq!(form["review"], "Still holds up.");
+    assert_eq!(form["tags"], "cinema,rewatch club");
+}
+
+#[tokio::test]
+async fn test_rate_like_and_delete() {
+    let server = start_film_site().await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    let rating = session.rate_film("the-matrix", Some(3.0)).await.unwrap();
+    let cleared = session.rate_film("the-matrix", None).await.unwrap();
+    let like = session.like_film("the-matrix", true).await.unwrap();
+    session.delete_diary_entry(9001).await.unwrap();
+
+    assert_eq!((rating.rating, cleared.rating, like.liked), (Some(3.0), None, true));
+    let ratings = posts(&server, "/s/film:51518/rate/");
+    assert_eq!((ratings[0]["rating"].as_str(), ratings[1]["rating"].as_str()), ("6", "0"));
+    assert_eq!(posts(&server, "/s/film:51518/like/")[0]["liked"], "true");
+    assert_eq!(posts(&server, "/s/viewing:9001/delete/").len(), 1);
+}
+
+#[tokio::test]
+async fn test_invalid_rating_is_rejected_before_submitting() {
+    let server = start_film_site().await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    let result = session.rate_film("the-matrix", Some(4.3)).await;
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+    assert!(server.requests().iter().all(|r| !r.target.starts_with("/s/")));
+}
+
+#[tokio::test]
+async fn test_rejected_form_reports_server_message() {
+    let server = start_film_site().await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+    let entry = LogEntry::new("the-matrix", NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()).review("fail");
+
+    let result = session.log_film(&entry).await;
+
+    assert!(matches!(result, Err(Error::FormRejected(ref message)) if message == "That film is not available."));
+}
+
+#[tokio::test]
+async fn test_watchlist_add_and_remove() {
+    let server = start_site_with(Arc::new(AtomicUsize::new(1)), |request| {
+        request.target.contains("watchlist").then(|| (200, Vec::new(), r#"{"result":true}"#.to_string()))
+    }).await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    session.add_to_watchlist("stalker").await.unwrap();
+    session.remove_from_watchlist("stalker").await.unwrap();
+
+    assert_eq!(posts(&server, "/film/stalker/add-to-watchlist/")[0]["__csrf"], "token-2");
+    assert_eq!(posts(&server, "/film/stalker/remove-from-watchlist/").len(), 1);
+}
+
+#[tokio::test]
+async fn test_clone_list_into_another_account() {
+    let (source, _) = common::mock_client(&[("https://letterboxd.com/testuser/list/favourite-heists/", "list_page.html")]);
+    let list = List::new_with_client(&source, "testuser", "favourite-heists").await.unwrap();
+    let server = start_site_with(Arc::new(AtomicUsize::new(1)), |request| {
+        (request.target == "/s/save-list").then(|| {
+            (200, Vec::new(), r#"{"result":true,"filmListId":777,"url":"/alice/list/favourite-heists/"}"#.to_string())
+        })
+    }).await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    let confirmation = session.clone_list(&list).await.unwrap();
+
+    assert_eq!(confirmation.list_id, 777);
+    assert_eq!(confirmation.url, Some(format!("{}/alice/list/favourite-heists/", server.url)));
+    let form = &posts(&server, "/s/save-list")[0];
+    assert_eq!(form["filmListId"], "");
+    assert_eq!(form["name"], "Favourite Heists");
+    assert_eq!(form["numberedList"], "true");
+    assert_eq!(form["tags"], "heist,crime");
+    assert_eq!(form["notes"], "Crews, plans and things going wrong.");
+    assert_eq!(form["publicList"], "true");
+    let entries: serde_json::Value = serde_json::from_str(&form["entries"]).unwrap();
+    let film_ids: Vec<_> = entries.as_array().unwrap().iter().map(|e| e["filmId"].as_u64().unwrap()).collect();
+    assert_eq!(film_ids, [1001, 1002, 1003]);
+    assert_eq!(entries[1]["review"], "The silent break-in.\n\nThirty minutes without a word.");
+}
+
+#[tokio::test]
+async fn test_reorder_keeps_a_private_list_private() {
+    let (source, _) = common::mock_client(&[
+        ("https://letterboxd.com/alice/list/watch-next/", "list_private.html"),
+        ("https://letterboxd.com/alice/list/watch-next/page/2/", "list_private_page_2.html"),
+    ]);
+    let list = List::new_with_client(&source, "alice", "watch-next").await.unwrap();
+    let server = start_site_with(Arc::new(AtomicUsize::new(1)), |request| {
+        (request.target == "/s/save-list").then(|| {
+            (200, Vec::new(), r#"{"result":true,"filmListId":5555,"url":"https://letterboxd.com/alice/list/watch-next/"}"#.to_string())
+        })
+    }).await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    let confirmation = session.reorder_list(&list, &["mirror", "stalker", "solaris"]).await.unwrap();
+
+    assert_eq!(confirmation.url.as_deref(), Some("https://letterboxd.com/alice/list/watch-next/"));
+
+    let form = &posts(&server, "/s/save-list")[0];
+    assert_eq!(form["publicList"], "false");
+    let entries: serde_json::Value = serde_json::from_str(&form["entries"]).unwrap();
+    let film_ids: Vec<_> = entries.as_array().unwrap().iter().map(|e| e["filmId"].as_u64().unwrap()).collect();
+    assert_eq!(film_ids, [2003, 2001, 2002]);
+    assert_eq!(entries[0]["review"], "Finally.");
+}
+
+#[tokio::test]
+async fn test_reorder_refuses_an_incomplete_list() {
+    let (source, _) = common::mock_client(&[
+        ("https://letterboxd.com/alice/list/watch-next/", "list_private.html"),
+        ("https://letterboxd.com/alice/list/watch-next/page/2/", "list_private_page_2.html"),
+    ]);
+    let mut list = List::new_with_client(&source, "alice", "watch-next").await.unwrap();
+    let server = start_site_with(Arc::new(AtomicUsize::new(1)), |_| None).await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    // As if a film failed to parse, so saving would drop it from the list
+    list.films.pop();
+    let result = session.reorder_list(&list, &["solaris", "stalker"]).await;
+
+    assert!(matches!(result, Err(Error::InvalidInput(_))));
+    assert!(posts(&server, "/s/save-list").is_empty());
+}
+
+#[tokio::test]
+async fn test_create_reorder_and_delete_list() {
+    let (source, _) = common::mock_client(&[("https://letterboxd.com/testuser/list/favourite-heists/", "list_page.html")]);
+    let list = List::new_with_client(&source, "testuser", "favourite-heists").await.unwrap();
+    let server = start_site_with(Arc::new(AtomicUsize::new(1)), |request| match request.target.as_str() {
+        "/film/the-matrix/" => Some((200, Vec::new(), common::fixture("film_the_matrix.html"))),
+        "/s/save-list" => Some((200, Vec::new(), r#"{"result":true,"filmListId":4321}"#.to_string())),
+        "/s/film-list:4321/delete/" => Some((200, Vec::new(), r#"{"result":true}"#.to_string())),
+        _ => None,
+    }).await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    let draft = ListDraft::new("Sci-fi").description("Notes on machines.").film_with_notes("the-matrix", "Red pill.");
+    let created = session.create_list(&draft).await.unwrap();
+    session.reorder_list(&list, &["thief", "heat-1995", "rififi"]).await.unwrap();
+    session.delete_list(created.list_id).await.unwrap();
+
+    let saves = posts(&server, "/s/save-list");
+    let created_entries: serde_json::Value = serde_json::from_str(&saves[0]["entries"]).unwrap();
+    assert_eq!(created_entries[0]["filmId"], 51518);
+    assert_eq!(created_entries[0]["review"], "Red pill.");
+    assert_eq!(saves[0]["notes"], "Notes on machines.");
+
+    assert_eq!(saves[1]["filmListId"], "4321");
+    let reordered: serde_json::Value = serde_json::from_str(&saves[1]["entries"]).unwrap();
+    let order: Vec<_> = reordered.as_array().unwrap().iter().map(|e| (e["filmId"].as_u64().unwrap(), e["position"].as_u64().unwrap())).collect();
+    assert_eq!(order, [(1003, 1), (1001, 2), (1002, 3)]);
+
+    assert_eq!(posts(&server, "/s/film-list:4321/delete/").len(), 1);
+}
+
+#[tokio::test]
+async fn test_follow_unfollow_and_block() {
+    let server = start_site_with(Arc::new(AtomicUsize::new(1)), |request| {
+        (request.method == "POST").then(|| (200, Vec::new(), r#"{"result":true}"#.to_string()))
+    }).await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    session.follow("bob").await.unwrap();
+    session.unfollow("bob").await.unwrap();
+    session.block("mallory").await.unwrap();
+
+    assert_eq!(posts(&server, "/bob/follow/").len(), 1);
+    assert_eq!(posts(&server, "/bob/unfollow/").len(), 1);
+    assert_eq!(posts(&server, "/mallory/block/")[0]["__csrf"], "token-2");
+    assert!(matches!(session.follow("alice").await, Err(Error::InvalidInput(_))));
+    assert!(matches!(session.follow("not a user").await, Err(Error::InvalidInput(_))));
+}
+
+#[tokio::test]
+async fn test_mirror_following_paces_and_retries_rate_limits() {
+    let carol_attempts = AtomicUsize::new(0);
+    let server = start_site_with(Arc::new(AtomicUsize::new(1)), move |request| match request.target.as_str() {
+        "/bob/following/" => Some((200, Vec::new(), common::fixture("user_following.html"))),
+        "/bob/following/page/2/" => Some((200, Vec::new(), common::fixture("user_following_page_2.html"))),
+        // alice already follows dave, linked with different casing
+        "/alice/following/" => Some((200, Vec::new(), common::fixture("user_following_page_2.html").replace("/dave/", "/Dave/"))),
+        "/carol/follow/" if carol_attempts.fetch_add(1, Ordering::SeqCst) == 0 => {
+            Some((429, vec![("Retry-After".to_string(), "0".to_string())], String::new()))
+        }
+        "/carol/follow/" => Some((200, Vec::new(), r#"{"result":true}"#.to_string())),
+        _ => None,
+    }).await;
+    let session = client_for(&server).login("alice", "secret").await.unwrap();
+
+    let report = session.mirror_following("bob", std::time::Duration::from_millis(1)).await.unwrap();
+
+    assert_eq!(report.followed, ["carol"]);
+    assert_eq!(report.already_following, ["dave"]);
+    assert!(report.failed.is_empty());
+    assert_eq!(posts(&server, "/carol/follow/").len(), 2);
+    assert!(posts(&server, "/alice/follow/").is_empty());
+    assert!(posts(&server, "/dave/follow/").is_empty());
+}
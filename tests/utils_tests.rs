@@ -1,4 +1,7 @@
-use rustboxd::utils::{is_valid_username, is_valid_rating, sanitize_for_url, extract_and_convert_shorthand};
+use rustboxd::Error;
+use rustboxd::utils::{is_valid_username, is_valid_rating, sanitize_for_url, extract_and_convert_shorthand, parse_shorthand};
+use rustboxd::utils::{parse_iso_date, parse_written_date};
+use rustboxd::utils::{build_film_url, build_diary_url, build_search_url, normalize_letterboxd_url, resize_avatar_url};
 
 #[test]
 fn test_username_validation() {
@@ -32,4 +35,41 @@ fn test_shorthand_conversion() {
     assert_eq!(extract_and_convert_shorthand("500"), 500);
     assert_eq!(extract_and_convert_shorthand("2.5M"), 2500000);
     assert_eq!(extract_and_convert_shorthand("invalid"), 0);
+
+    assert_eq!(parse_shorthand("3.9M"), Some(3900000));
+    assert_eq!(parse_shorthand("0"), Some(0));
+    assert_eq!(parse_shorthand("—"), None);
+}
+
+#[test]
+fn test_url_builders_use_base_url() {
+    let base = "http://localhost:8080";
+    assert_eq!(build_film_url(base, "the-matrix"), "http://localhost:8080/film/the-matrix/");
+    assert_eq!(build_diary_url(base, "user", Some(2024), Some(3), None), "http://localhost:8080/user/films/diary/for/2024/03/");
+    assert_eq!(build_search_url(base, "blade runner", Some("films")), "http://localhost:8080/s/search/films/blade%20runner/");
+    assert_eq!(normalize_letterboxd_url(base, "/film/alien/"), "http://localhost:8080/film/alien/");
+    assert_eq!(normalize_letterboxd_url(base, "https://letterboxd.com/film/alien/"), "https://letterboxd.com/film/alien/");
+}
+
+#[test]
+fn test_resize_avatar_url() {
+    assert_eq!(
+        resize_avatar_url("https://a.ltrbxd.com/resized/avatar/upload/1/2/avtr-0-220-0-220-crop.jpg?v=1", 80),
+        "https://a.ltrbxd.com/resized/avatar/upload/1/2/avtr-0-80-0-80-crop.jpg?v=1"
+    );
+    assert_eq!(
+        resize_avatar_url("https://s.ltrbxd.com/static/img/avatar220.png", 1000),
+        "https://s.ltrbxd.com/static/img/avatar1000.png"
+    );
+    assert_eq!(resize_avatar_url("https://example.com/me.jpg", 80), "https://example.com/me.jpg");
+}
+
+#[test]
+fn test_date_parsing() {
+    assert_eq!(parse_iso_date("2024-03-09T12:00:00Z").unwrap(), (2024, 3, 9));
+    assert_eq!(parse_written_date("09 Mar 2024").unwrap(), (2024, 3, 9));
+
+    assert!(matches!(parse_iso_date("2024-xx-09"), Err(Error::Parse { url, .. }) if url == "2024-xx-09"));
+    assert!(matches!(parse_written_date("9 March"), Err(Error::Parse { .. })));
+    assert!(matches!(parse_written_date("09 Foo 2024"), Err(Error::Parse { context, .. }) if context == "invalid month"));
 }